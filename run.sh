@@ -2,7 +2,7 @@
 
 if [ $# -eq 0 ]; then
     echo "Usage: $0 [wsl|wasm|lint|auto]"
-    echo "auto - Run the game via _cargo run with some debugging features"
+    echo "auto - Run the game via _cargo run with some debugging features and hot reloading of assets/config"
     echo "wsl  - Run the game in WSL2 Environment"
     echo "wasm - Run the game in web browser"
     echo "       Make sure you have installed [wasm-server-runner](https://github.com/jakobhellermann/wasm-server-runner)"
@@ -30,7 +30,7 @@ case "$platform" in
         clear && cargo fmt && cargo clippy --workspace --all-targets --all-features -- -Dwarnings
         ;;
     "auto")
-        clear && cargo run --features debug
+        clear && cargo run --features debug,hot_reload
         ;;
     *)
         echo "Error: Unknown command. Use wsl, wasm, lint or auto"
@@ -1,4 +1,4 @@
-use crate::GameState;
+use crate::{hostile_entity::HostilesConfig, GameState};
 use bevy::prelude::*;
 use bevy_asset_loader::prelude::*;
 use bevy_ecs_ldtk::assets::LdtkProject;
@@ -41,6 +41,9 @@ pub struct World {
     pub ldtk: Handle<LdtkProject>,
     #[asset(path = "Legacy_Adventure_Pack/Background.png")]
     pub background: Handle<Image>,
+    /// Hostiles are spawned along with the level, so they have to be known before
+    #[asset(path = "config/hostiles.ron")]
+    pub hostiles: Handle<HostilesConfig>,
 }
 
 #[derive(AssetCollection, Resource, Clone)]
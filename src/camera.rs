@@ -1,4 +1,8 @@
-use crate::{player::PlayerEntity, ASPECT_RATIO};
+use crate::{
+    player::PlayerEntity,
+    settings::{Settings, ViewportScaling},
+    ASPECT_RATIO,
+};
 use bevy::{
     prelude::*,
     render::camera::{ScalingMode, Viewport},
@@ -20,12 +24,6 @@ impl Plugin for MainCameraPlugin {
 pub struct MainCamera;
 
 fn spawn_camera(mut commands: Commands) {
-    let mut orthographic_project = OrthographicProjection::default_2d();
-    orthographic_project.scaling_mode = ScalingMode::AutoMin {
-        min_width: 1280.,
-        min_height: 720.,
-    };
-
     commands.spawn(MainCamera).insert(OrthographicProjection {
         scaling_mode: ScalingMode::AutoMin {
             min_width: 1280.,
@@ -36,13 +34,23 @@ fn spawn_camera(mut commands: Commands) {
 }
 
 fn update_camera_viewport(
-    window_query: Query<&Window, Changed<Window>>,
+    window_query: Query<Ref<Window>>,
     mut camera_query: Query<&mut Camera, With<MainCamera>>,
+    settings: Res<Settings>,
 ) {
     for window in &window_query {
+        if !window.is_changed() && !settings.is_changed() {
+            continue;
+        }
+
         let size = window.size();
 
         for mut camera in &mut camera_query {
+            if settings.viewport_scaling == ViewportScaling::Fill {
+                camera.viewport = None;
+                continue;
+            }
+
             let aspect_ratio = size.x / size.y;
             let viewport_width = if aspect_ratio > ASPECT_RATIO {
                 size.y * ASPECT_RATIO
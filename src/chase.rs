@@ -0,0 +1,399 @@
+use crate::{
+    assets,
+    hostile_entity::{HostileEntity, Patrol},
+    player::PlayerEntity,
+    utils::{maybe_float_field, Maybe},
+    GameState, GRID_SIZE,
+};
+use bevy::prelude::*;
+use bevy_ecs_ldtk::{
+    prelude::*,
+    utils::{grid_coords_to_translation, translation_to_grid_coords},
+};
+use bevy_rapier2d::prelude::*;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Hostiles with an `aggro_radius` leave their [`Patrol`] to chase the player once they
+/// see it, see [`Chase`]
+pub struct ChasePlugin;
+
+impl Plugin for ChasePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(NavGrid::default())
+            .add_systems(Update, build_nav_grid)
+            .add_systems(
+                Update,
+                (spot_player, chase_player)
+                    .chain()
+                    .run_if(in_state(GameState::PlayingScreen)),
+            );
+    }
+}
+
+/// Seconds without seeing the player before a chasing hostile gives up
+const LOSE_SIGHT_TIME: f32 = 2.;
+/// Seconds between path updates while chasing
+const REPATH_INTERVAL: f32 = 0.5;
+
+/// Cells of the current level that hostiles can move through, built from the
+/// `Collider` IntGrid layer
+#[derive(Resource, Default)]
+pub struct NavGrid {
+    pub width: i32,
+    pub height: i32,
+    /// Indexed by `y * width + x` of [`GridCoords`]
+    pub walkable: Vec<bool>,
+}
+
+impl NavGrid {
+    pub fn is_walkable(&self, coords: GridCoords) -> bool {
+        coords.x >= 0
+            && coords.y >= 0
+            && coords.x < self.width
+            && coords.y < self.height
+            && self.walkable[(coords.y * self.width + coords.x) as usize]
+    }
+
+    /// Walkable cells right above a solid cell, the only cells ground hostiles can be in
+    pub fn is_standable(&self, coords: GridCoords) -> bool {
+        let below = coords + GridCoords::new(0, -1);
+        self.is_walkable(coords) && below.y >= 0 && !self.is_walkable(below)
+    }
+
+    /// The cell a ground hostile would stand on below `coords`, i.e. under a jumping player
+    fn floor_below(&self, mut coords: GridCoords) -> Option<GridCoords> {
+        while self.is_walkable(coords) {
+            if self.is_standable(coords) {
+                return Some(coords);
+            }
+            coords.y -= 1;
+        }
+        None
+    }
+
+    /// A* from `start` to `goal` through walkable cells, the returned path excludes `start`.
+    ///
+    /// Flying hostiles move through every walkable cell, ground hostiles only walk
+    /// along the floor and neither climb nor drop down ledges, so their `goal` is
+    /// moved down onto the floor.
+    pub fn find_path(
+        &self,
+        start: GridCoords,
+        goal: GridCoords,
+        flying: bool,
+    ) -> Option<Vec<GridCoords>> {
+        let goal = if flying {
+            goal
+        } else {
+            self.floor_below(goal)?
+        };
+        let can_enter = |coords: GridCoords| {
+            if flying {
+                self.is_walkable(coords)
+            } else {
+                self.is_standable(coords)
+            }
+        };
+
+        if !self.is_walkable(start) || !can_enter(goal) {
+            return None;
+        }
+
+        let index = |coords: GridCoords| (coords.y * self.width + coords.x) as usize;
+        let heuristic = |coords: GridCoords| (coords.x - goal.x).abs() + (coords.y - goal.y).abs();
+
+        let mut cost = vec![i32::MAX; self.walkable.len()];
+        let mut came_from: Vec<Option<GridCoords>> = vec![None; self.walkable.len()];
+        let mut open = BinaryHeap::new();
+
+        cost[index(start)] = 0;
+        open.push(Reverse((heuristic(start), start.x, start.y)));
+
+        while let Some(Reverse((_, x, y))) = open.pop() {
+            let current = GridCoords::new(x, y);
+
+            if current == goal {
+                let mut path = Vec::new();
+                let mut cell = goal;
+                while cell != start {
+                    path.push(cell);
+                    cell = came_from[index(cell)]?;
+                }
+                path.reverse();
+                return Some(path);
+            }
+
+            for neighbour in [
+                current + GridCoords::new(1, 0),
+                current + GridCoords::new(-1, 0),
+                current + GridCoords::new(0, 1),
+                current + GridCoords::new(0, -1),
+            ] {
+                if !can_enter(neighbour) {
+                    continue;
+                }
+
+                let new_cost = cost[index(current)] + 1;
+                if new_cost < cost[index(neighbour)] {
+                    cost[index(neighbour)] = new_cost;
+                    came_from[index(neighbour)] = Some(current);
+                    open.push(Reverse((
+                        new_cost + heuristic(neighbour),
+                        neighbour.x,
+                        neighbour.y,
+                    )));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub enum ChaseState {
+    #[default]
+    Patrolling,
+    Chasing,
+    /// Going back to the patrol route after losing the player
+    Returning,
+}
+
+/// Set through the optional `aggro_radius` (in tiles) and `chase_speed` LDtk fields,
+/// `flying` comes from the [`crate::hostile_entity::HostileDefinition`]
+#[derive(Component, Debug, Default)]
+pub struct Chase {
+    /// Pixels
+    pub aggro_radius: f32,
+    /// Pixels per second
+    pub chase_speed: f32,
+    /// See [`NavGrid::find_path`]
+    pub flying: bool,
+    pub state: ChaseState,
+    /// Cells left to walk through, the first one is walked to next
+    pub path: Vec<GridCoords>,
+    pub time_since_seen: f32,
+    pub time_since_repath: f32,
+}
+
+impl Chase {
+    pub fn is_patrolling(&self) -> bool {
+        self.state == ChaseState::Patrolling
+    }
+
+    /// Goes back to patrolling right away, i.e. when the hostile is reset
+    pub fn reset(&mut self) {
+        self.state = ChaseState::Patrolling;
+        self.path.clear();
+    }
+}
+
+impl From<&EntityInstance> for Maybe<Chase> {
+    fn from(value: &EntityInstance) -> Self {
+        let Some(aggro_radius) = maybe_float_field(value, "aggro_radius") else {
+            return Self::NONE;
+        };
+
+        Self::new(Chase {
+            aggro_radius: aggro_radius * GRID_SIZE as f32,
+            chase_speed: maybe_float_field(value, "chase_speed").unwrap_or(70.),
+            ..default()
+        })
+    }
+}
+
+fn build_nav_grid(
+    mut level_events: EventReader<LevelEvent>,
+    world: Res<assets::World>,
+    ldtk_project_assets: Res<Assets<LdtkProject>>,
+    mut nav_grid: ResMut<NavGrid>,
+) {
+    for level_event in level_events.read() {
+        let LevelEvent::Spawned(level_iid) = level_event else {
+            continue;
+        };
+
+        let Some(level) = ldtk_project_assets
+            .get(&world.ldtk)
+            .and_then(|ldtk_project| {
+                ldtk_project
+                    .as_standalone()
+                    .get_loaded_level_by_iid(&level_iid.to_string())
+            })
+        else {
+            continue;
+        };
+
+        let Some(collider_layer) = level
+            .layer_instances()
+            .iter()
+            .find(|layer| layer.identifier == "Collider")
+        else {
+            log::error!("Level {} has no Collider layer to navigate", level_iid);
+            continue;
+        };
+
+        let (width, height) = (collider_layer.c_wid, collider_layer.c_hei);
+        let mut walkable = vec![false; (width * height) as usize];
+
+        // The CSV starts at the top, while `GridCoords` start at the bottom
+        for (i, value) in collider_layer.int_grid_csv.iter().enumerate() {
+            let (x, ldtk_y) = (i as i32 % width, i as i32 / width);
+            let y = height - 1 - ldtk_y;
+            walkable[(y * width + x) as usize] = *value == 0;
+        }
+
+        log::info!("Built navigation grid for level {}", level_iid);
+        *nav_grid = NavGrid {
+            width,
+            height,
+            walkable,
+        };
+    }
+}
+
+fn spot_player(
+    player_query: Query<&GlobalTransform, With<PlayerEntity>>,
+    mut hostile_query: Query<(Entity, &GlobalTransform, &mut Chase), With<HostileEntity>>,
+    rapier_context: ReadRapierContext,
+    nav_grid: Res<NavGrid>,
+    time: Res<Time>,
+) {
+    let grid_size = IVec2::splat(GRID_SIZE);
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
+    let player_position = player_transform.translation().truncate();
+    let rapier_context = rapier_context.single();
+
+    for (hostile_entity, hostile_transform, mut chase) in &mut hostile_query {
+        let hostile_position = hostile_transform.translation().truncate();
+        let to_player = player_position - hostile_position;
+
+        // Only walls block the view, the ray spans exactly the distance to the player
+        let can_see_player = to_player.length() <= chase.aggro_radius
+            && rapier_context
+                .cast_ray(
+                    hostile_position,
+                    to_player,
+                    1.,
+                    true,
+                    QueryFilter::only_fixed()
+                        .exclude_sensors()
+                        .exclude_collider(hostile_entity),
+                )
+                .is_none();
+
+        if can_see_player {
+            chase.time_since_seen = 0.;
+
+            // i.e. ground hostiles ignore a player on a ledge they can't get onto
+            let can_reach_player = || {
+                nav_grid
+                    .find_path(
+                        translation_to_grid_coords(hostile_position, grid_size),
+                        translation_to_grid_coords(player_position, grid_size),
+                        chase.flying,
+                    )
+                    .is_some()
+            };
+
+            if chase.state != ChaseState::Chasing && can_reach_player() {
+                log::info!("Hostile spotted the player");
+                chase.state = ChaseState::Chasing;
+                // Find a path right away
+                chase.time_since_repath = REPATH_INTERVAL;
+            }
+        } else if chase.state == ChaseState::Chasing {
+            chase.time_since_seen += time.delta_secs();
+
+            if chase.time_since_seen >= LOSE_SIGHT_TIME {
+                log::info!("Hostile lost the player");
+                chase.state = ChaseState::Returning;
+                chase.path.clear();
+            }
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn chase_player(
+    player_query: Query<&GlobalTransform, With<PlayerEntity>>,
+    mut hostile_query: Query<(&Transform, &mut Velocity, &mut Chase, &Patrol), With<HostileEntity>>,
+    nav_grid: Res<NavGrid>,
+    time: Res<Time>,
+) {
+    let grid_size = IVec2::splat(GRID_SIZE);
+    let player_coords = player_query
+        .get_single()
+        .ok()
+        .map(|transform| translation_to_grid_coords(transform.translation().truncate(), grid_size));
+
+    for (transform, mut velocity, mut chase, patrol) in &mut hostile_query {
+        let position = transform.translation.truncate();
+        let coords = translation_to_grid_coords(position, grid_size);
+
+        match chase.state {
+            ChaseState::Patrolling => continue,
+            ChaseState::Chasing => {
+                chase.time_since_repath += time.delta_secs();
+
+                if chase.time_since_repath >= REPATH_INTERVAL {
+                    chase.time_since_repath = 0.;
+
+                    match player_coords
+                        .and_then(|goal| nav_grid.find_path(coords, goal, chase.flying))
+                    {
+                        Some(path) => chase.path = path,
+                        // The player got somewhere the hostile can't follow, it isn't
+                        // spotted again until it can be reached
+                        None => {
+                            log::info!("Hostile can't reach the player");
+                            chase.state = ChaseState::Returning;
+                            chase.path.clear();
+                        }
+                    }
+                }
+            }
+            ChaseState::Returning => {
+                if chase.path.is_empty() {
+                    // Back to where the current leg of the patrol started, so that the
+                    // patrol continues towards its next point from there
+                    let Some(leg_start) = patrol.leg_start() else {
+                        chase.reset();
+                        continue;
+                    };
+                    let goal = translation_to_grid_coords(leg_start, grid_size);
+
+                    match nav_grid.find_path(coords, goal, chase.flying) {
+                        Some(path) if !path.is_empty() => chase.path = path,
+                        // Already back on the route, or there is no way back
+                        _ => {
+                            chase.reset();
+                            velocity.linvel = Vec2::ZERO;
+                            continue;
+                        }
+                    }
+                }
+            }
+        }
+
+        let Some(&next_cell) = chase.path.first() else {
+            velocity.linvel = Vec2::ZERO;
+            continue;
+        };
+
+        let to_next_cell = grid_coords_to_translation(next_cell, grid_size) - position;
+
+        if to_next_cell.length() <= chase.chase_speed * time.delta_secs() {
+            chase.path.remove(0);
+
+            if chase.path.is_empty() && chase.state == ChaseState::Returning {
+                chase.reset();
+            }
+        }
+
+        velocity.linvel = to_next_cell.normalize_or_zero() * chase.chase_speed;
+    }
+}
@@ -0,0 +1,213 @@
+use crate::{
+    assets::AudioAssets,
+    camera::MainCamera,
+    chase::Chase,
+    hostile_entity::{Facing, HostileEntity, Patrol},
+    level_manager::CurrentLevelInfo,
+    player::{
+        Blinded, DashState, HealthBar, HostileContacts, Invulnerable, JumpState, PlayerEntity,
+    },
+    screens::game_over_screen::RestartGameEvent,
+    GameState, GRID_SIZE,
+};
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+use bevy_light_2d::prelude::{AmbientLight2d, PointLight2d};
+use bevy_rapier2d::prelude::*;
+
+pub struct CheckpointPlugin;
+
+impl Plugin for CheckpointPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_ldtk_entity::<Checkpoint>("Checkpoint")
+            .add_event::<RespawnAtCheckpointEvent>()
+            .insert_resource(ActiveCheckpoint::default())
+            .add_systems(
+                Update,
+                activate_checkpoint.run_if(in_state(GameState::PlayingScreen)),
+            )
+            .add_systems(Update, (reset_active_checkpoint, respawn_at_checkpoint));
+    }
+}
+
+#[derive(better_default::Default, Bundle, LdtkEntity)]
+#[default(
+    collider: Collider::cuboid(GRID_SIZE as f32 / 2., GRID_SIZE as f32),
+    active_events: ActiveEvents::COLLISION_EVENTS,
+    point_light: PointLight2d {
+        intensity: 0.3,
+        radius: GRID_SIZE as f32 * 2.,
+        ..default()
+    }
+)]
+pub struct Checkpoint {
+    #[sprite_sheet]
+    pub sprite: Sprite,
+    pub checkpoint_entity: CheckpointEntity,
+    pub collider: Collider,
+    pub sensor: Sensor,
+    pub active_events: ActiveEvents,
+    pub point_light: PointLight2d,
+}
+
+#[derive(Default, Component)]
+pub struct CheckpointEntity;
+
+/// Where the player respawns in the current level, `None` until a checkpoint is touched
+#[derive(Resource, Default)]
+pub struct ActiveCheckpoint(pub Option<Vec2>);
+
+/// Resets the player and hostile entities of the current level, but unlike
+/// [`RestartGameEvent`] the level isn't respawned and the time keeps running.
+#[derive(Event)]
+pub struct RespawnAtCheckpointEvent;
+
+fn activate_checkpoint(
+    mut collision_events: EventReader<CollisionEvent>,
+    player_query: Query<Entity, With<PlayerEntity>>,
+    mut checkpoint_query: Query<(Entity, &Transform, &mut PointLight2d), With<CheckpointEntity>>,
+    mut active_checkpoint: ResMut<ActiveCheckpoint>,
+    audio_assets: Res<AudioAssets>,
+    mut commands: Commands,
+) {
+    let Ok(player_entity) = player_query.get_single() else {
+        return;
+    };
+
+    for collision in collision_events.read() {
+        if let CollisionEvent::Started(entity_one, entity_two, ..) = *collision {
+            if entity_one != player_entity && entity_two != player_entity {
+                continue;
+            }
+
+            let Some(position) = checkpoint_query
+                .iter()
+                .find(|(entity, ..)| *entity == entity_one || *entity == entity_two)
+                .map(|(_, transform, _)| transform.translation.truncate())
+            else {
+                continue;
+            };
+
+            if active_checkpoint.0 == Some(position) {
+                continue;
+            }
+
+            log::info!("Activating Checkpoint at {:?}", position);
+            active_checkpoint.0 = Some(position);
+
+            commands.spawn((
+                AudioPlayer(audio_assets.jump_boost.clone()),
+                PlaybackSettings::REMOVE,
+            ));
+
+            // Only the latest checkpoint is lit up
+            for (_, transform, mut point_light) in &mut checkpoint_query {
+                let is_active = transform.translation.truncate() == position;
+                point_light.intensity = if is_active { 1. } else { 0.3 };
+                point_light.radius = GRID_SIZE as f32 * if is_active { 4. } else { 2. };
+            }
+        }
+    }
+}
+
+fn reset_active_checkpoint(
+    current_level_info: Res<CurrentLevelInfo>,
+    mut restart_game_events: EventReader<RestartGameEvent>,
+    mut active_checkpoint: ResMut<ActiveCheckpoint>,
+) {
+    let restarted = restart_game_events.read().count() > 0;
+
+    if (current_level_info.is_changed() || restarted) && active_checkpoint.0.is_some() {
+        log::info!("Resetting Checkpoint");
+        active_checkpoint.0 = None;
+    }
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn respawn_at_checkpoint(
+    mut events: EventReader<RespawnAtCheckpointEvent>,
+    active_checkpoint: Res<ActiveCheckpoint>,
+    mut player_query: Query<
+        (
+            Entity,
+            &mut Transform,
+            &mut Velocity,
+            &mut HealthBar,
+            &mut JumpState,
+            &mut DashState,
+            &mut HostileContacts,
+            &mut Sprite,
+        ),
+        With<PlayerEntity>,
+    >,
+    mut hostile_query: Query<
+        (
+            &mut Transform,
+            &mut Velocity,
+            &mut Patrol,
+            &mut Facing,
+            Option<&mut Chase>,
+        ),
+        (With<HostileEntity>, Without<PlayerEntity>),
+    >,
+    main_camera_query: Query<Entity, With<MainCamera>>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+    mut time: ResMut<Time<Virtual>>,
+    mut commands: Commands,
+) {
+    for _ in events.read() {
+        let Some(position) = active_checkpoint.0 else {
+            log::error!("Tried to respawn without an active checkpoint");
+            return;
+        };
+
+        for (
+            player_entity,
+            mut transform,
+            mut velocity,
+            mut health_bar,
+            mut jump_state,
+            mut dash_state,
+            mut hostile_contacts,
+            mut sprite,
+        ) in &mut player_query
+        {
+            log::info!("Respawning at Checkpoint {:?}", position);
+            transform.translation.x = position.x;
+            transform.translation.y = position.y;
+            *velocity = Velocity::zero();
+            // Heart containers picked up before the checkpoint are kept
+            health_bar.health = health_bar.max;
+            *jump_state = JumpState::default();
+            *dash_state = DashState::default();
+            hostile_contacts.0.clear();
+            sprite.color.set_alpha(1.);
+
+            commands
+                .entity(player_entity)
+                .remove::<(Invulnerable, Blinded)>()
+                .insert(PointLight2d {
+                    intensity: 1.,
+                    ..default()
+                });
+        }
+
+        for (mut transform, mut velocity, mut patrol, mut facing, chase) in &mut hostile_query {
+            let start = patrol.reset();
+            if let Some(mut chase) = chase {
+                chase.reset();
+            }
+            transform.translation.x = start.x;
+            transform.translation.y = start.y;
+            *velocity = Velocity::zero();
+            facing.direction = 1.;
+        }
+
+        for main_camera in &main_camera_query {
+            commands.entity(main_camera).remove::<AmbientLight2d>();
+        }
+
+        next_game_state.set(GameState::PlayingScreen);
+        time.unpause();
+    }
+}
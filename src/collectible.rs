@@ -0,0 +1,293 @@
+use crate::{
+    assets::{self, AssetsLoadingState, AudioAssets, FontAssets},
+    level_manager::{AllLevels, CurrentLevelInfo},
+    player::PlayerEntity,
+    save::{self, SaveData},
+    screens::despawn_screen,
+    time::{RecordTimeEvent, RestartTimeEvent, TimeTakenRes},
+    GameState, GRID_SIZE,
+};
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+use bevy_light_2d::prelude::PointLight2d;
+use bevy_rapier2d::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+pub struct CollectiblePlugin;
+
+impl Plugin for CollectiblePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_ldtk_entity::<Collectible>("Collectible")
+            .add_systems(Startup, load_collected_items)
+            .add_systems(OnEnter(AssetsLoadingState::Loaded), count_collectibles)
+            .add_systems(OnEnter(GameState::PlayingScreen), spawn_collectible_counter)
+            .add_systems(
+                OnExit(GameState::PlayingScreen),
+                despawn_screen::<CollectibleCounterContext>,
+            )
+            .add_systems(
+                Update,
+                (
+                    mark_collected,
+                    pick_up_collectible,
+                    update_collectible_counter,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::PlayingScreen)),
+            )
+            // After every system that finishes or restarts a level
+            .add_systems(
+                PostUpdate,
+                (store_collected_items, forget_collecting).chain(),
+            );
+    }
+}
+
+#[derive(better_default::Default, Bundle, LdtkEntity)]
+#[default(
+    collider: Collider::ball(GRID_SIZE as f32 / 3.),
+    active_events: ActiveEvents::COLLISION_EVENTS,
+    point_light: PointLight2d {
+        intensity: 0.5,
+        radius: GRID_SIZE as f32,
+        ..default()
+    }
+)]
+pub struct Collectible {
+    #[sprite_sheet]
+    pub sprite: Sprite,
+    pub collectible_entity: CollectibleEntity,
+    pub collider: Collider,
+    pub sensor: Sensor,
+    pub active_events: ActiveEvents,
+    pub point_light: PointLight2d,
+}
+
+#[derive(Default, Component)]
+pub struct CollectibleEntity;
+
+/// Collectibles that were picked up in an earlier run, they are shown faded and
+/// can't be picked up again
+#[derive(Component)]
+pub struct AlreadyCollected;
+
+/// Persisted version of [`TimeTakenRes::collected`], only stored once a level is finished
+/// so that dying or restarting the level drops what was picked up in that attempt.
+///
+/// It has its own save slot instead of being part of [`crate::time::BestTimes`], so that
+/// best times saved before collectibles existed stay valid and a corrupt slot only loses
+/// one of the two.
+#[derive(Serialize, Deserialize, Default)]
+pub struct CollectedItems {
+    pub levels: BTreeMap<i32, BTreeSet<String>>,
+}
+
+impl SaveData for CollectedItems {
+    const KEY: &'static str = "collected_items";
+    const VERSION: u32 = 1;
+}
+
+fn load_collected_items(mut time_taken_res: ResMut<TimeTakenRes>) {
+    let collected_items = save::load::<CollectedItems>();
+    time_taken_res.collected = collected_items.levels.into_iter().collect();
+}
+
+/// Counts the collectibles of every level, so that the totals are known before
+/// a level is played
+fn count_collectibles(
+    world: Res<assets::World>,
+    ldtk_project_assets: Res<Assets<LdtkProject>>,
+    all_levels: Res<AllLevels>,
+    mut time_taken_res: ResMut<TimeTakenRes>,
+) {
+    let Some(ldtk_project) = ldtk_project_assets.get(&world.ldtk) else {
+        log::error!("LDtk project should be loaded to count collectibles");
+        return;
+    };
+
+    for (level_id, level_iid) in &all_levels.all_levels {
+        let Some(level) = ldtk_project
+            .as_standalone()
+            .iter_raw_levels()
+            .find(|level| level.iid == *level_iid)
+        else {
+            continue;
+        };
+
+        let total = level
+            .layer_instances
+            .iter()
+            .flatten()
+            .flat_map(|layer| &layer.entity_instances)
+            .filter(|entity| entity.identifier == "Collectible")
+            .count();
+
+        time_taken_res.collectible_totals.insert(*level_id, total);
+    }
+}
+
+fn mark_collected(
+    query: Query<(Entity, &EntityIid), Added<CollectibleEntity>>,
+    mut sprite_query: Query<&mut Sprite>,
+    current_level_info: Res<CurrentLevelInfo>,
+    time_taken_res: Res<TimeTakenRes>,
+    mut commands: Commands,
+) {
+    let Some(collected) = time_taken_res
+        .collected
+        .get(&current_level_info.current_level_id)
+    else {
+        return;
+    };
+
+    for (entity, entity_iid) in &query {
+        if collected.contains(entity_iid.as_str()) {
+            if let Ok(mut sprite) = sprite_query.get_mut(entity) {
+                sprite.color.set_alpha(0.3);
+            }
+            commands
+                .entity(entity)
+                .insert(AlreadyCollected)
+                .remove::<PointLight2d>();
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn pick_up_collectible(
+    mut collision_events: EventReader<CollisionEvent>,
+    player_query: Query<Entity, With<PlayerEntity>>,
+    collectible_query: Query<
+        (Entity, &EntityIid),
+        (With<CollectibleEntity>, Without<AlreadyCollected>),
+    >,
+    current_level_info: Res<CurrentLevelInfo>,
+    mut time_taken_res: ResMut<TimeTakenRes>,
+    audio_assets: Res<AudioAssets>,
+    mut commands: Commands,
+) {
+    let Ok(player_entity) = player_query.get_single() else {
+        return;
+    };
+
+    for collision in collision_events.read() {
+        if let CollisionEvent::Started(entity_one, entity_two, ..) = *collision {
+            if entity_one != player_entity && entity_two != player_entity {
+                continue;
+            }
+
+            for (collectible_entity, entity_iid) in &collectible_query {
+                if entity_one != collectible_entity && entity_two != collectible_entity {
+                    continue;
+                }
+
+                log::info!("Picked up Collectible {}", entity_iid.as_str());
+                commands.entity(collectible_entity).despawn_recursive();
+                commands.spawn((
+                    AudioPlayer(audio_assets.button.clone()),
+                    PlaybackSettings::REMOVE,
+                ));
+
+                time_taken_res
+                    .collecting
+                    .entry(current_level_info.current_level_id)
+                    .or_default()
+                    .insert(entity_iid.as_str().to_string());
+            }
+        }
+    }
+}
+
+/// The collectibles picked up in a finished level count as collected from now on
+fn store_collected_items(
+    mut record_time_events: EventReader<RecordTimeEvent>,
+    mut time_taken_res: ResMut<TimeTakenRes>,
+) {
+    if record_time_events.read().count() == 0 {
+        return;
+    }
+
+    let time_taken_res = time_taken_res.as_mut();
+    let mut is_new = false;
+    for (level_id, collecting) in time_taken_res.collecting.drain() {
+        let collected = time_taken_res.collected.entry(level_id).or_default();
+        for entity_iid in collecting {
+            is_new |= collected.insert(entity_iid);
+        }
+    }
+
+    if is_new {
+        save::store(&CollectedItems {
+            levels: time_taken_res
+                .collected
+                .iter()
+                .map(|(level_id, collected)| (*level_id, collected.clone()))
+                .collect(),
+        });
+    }
+}
+
+/// Collectibles of an unfinished attempt have to be picked up again
+fn forget_collecting(
+    mut restart_time_events: EventReader<RestartTimeEvent>,
+    mut time_taken_res: ResMut<TimeTakenRes>,
+) {
+    if restart_time_events.read().count() > 0 {
+        time_taken_res.collecting.clear();
+    }
+}
+
+#[derive(Component)]
+pub struct CollectibleCounterContext;
+
+#[derive(Component)]
+pub struct CollectibleCounterText;
+
+fn spawn_collectible_counter(mut commands: Commands, font_assets: Res<FontAssets>) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                right: Val::Px(10.),
+                top: Val::Px(95.),
+                ..default()
+            },
+            CollectibleCounterContext,
+        ))
+        .with_child((
+            Text::new(""),
+            TextFont {
+                font: font_assets.default_font.clone(),
+                font_size: 24.,
+                ..default()
+            },
+            TextColor(Color::WHITE),
+            CollectibleCounterText,
+        ));
+}
+
+fn update_collectible_counter(
+    mut query: Query<&mut Text, With<CollectibleCounterText>>,
+    time_taken_res: Res<TimeTakenRes>,
+    current_level_info: Res<CurrentLevelInfo>,
+) {
+    let level_id = current_level_info.current_level_id;
+
+    for mut text in &mut query {
+        // Levels without collectibles don't need a counter
+        let counter = if time_taken_res.collectible_total(level_id) > 0 {
+            format!(
+                "Collected {}/{}",
+                time_taken_res.picked_up_count(level_id),
+                time_taken_res.collectible_total(level_id)
+            )
+        } else {
+            String::new()
+        };
+
+        if text.0 != counter {
+            text.0 = counter;
+        }
+    }
+}
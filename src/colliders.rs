@@ -35,15 +35,13 @@ impl From<&EntityInstance> for ColliderBundle {
                 velocity: Maybe::new(Velocity::default()),
                 ..default()
             },
-            "Sand_Ghoul" | "Grave_Revenant" | "Mutilated_Stumbler" | "Adept_Necromancer" => {
-                ColliderBundle {
-                    rigid_body: Maybe::new(RigidBody::KinematicVelocityBased),
-                    collider: Collider::cuboid(GRID_SIZE as f32 / 2., GRID_SIZE as f32 / 2.),
-                    rotation_constraints,
-                    velocity: Maybe::new(Velocity::default()),
-                    ..default()
-                }
-            }
+            "Moving_Platform" => ColliderBundle {
+                rigid_body: Maybe::new(RigidBody::KinematicVelocityBased),
+                collider: Collider::cuboid(value.width as f32 / 2., value.height as f32 / 2.),
+                rotation_constraints,
+                velocity: Maybe::new(Velocity::default()),
+                ..default()
+            },
             _ => ColliderBundle::default(),
         }
     }
@@ -51,12 +49,12 @@ impl From<&EntityInstance> for ColliderBundle {
 
 impl From<IntGridCell> for ColliderBundle {
     fn from(value: IntGridCell) -> Self {
-        if value.value == 1 {
-            return Self {
+        match value.value {
+            1..=3 => Self {
                 collider: Collider::cuboid(GRID_SIZE as f32 / 2., GRID_SIZE as f32 / 2.),
                 ..default()
-            };
+            },
+            value => panic!("Unsupported IntGridCell value {value}"),
         }
-        panic!("Unsupported IntGridCell value");
     }
 }
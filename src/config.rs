@@ -0,0 +1,97 @@
+use bevy::asset::{io::Reader, AssetLoader, LoadContext};
+use bevy::prelude::*;
+use serde::de::DeserializeOwned;
+use std::marker::PhantomData;
+
+/// Values that are tuned by designers in a RON file under `assets/`
+pub trait RonConfig: Asset + Resource + Clone + Default + DeserializeOwned {}
+
+impl<T: Asset + Resource + Clone + Default + DeserializeOwned> RonConfig for T {}
+
+/// Loads `T` from the RON file at `path` and keeps the `T` resource in sync with it.
+///
+/// The resource is `T::default()` until the file is loaded, build with the `hot_reload`
+/// feature to apply changes to the file while the game is running.
+pub struct RonConfigPlugin<T> {
+    path: &'static str,
+    _marker: PhantomData<T>,
+}
+
+impl<T> RonConfigPlugin<T> {
+    pub fn new(path: &'static str) -> Self {
+        Self {
+            path,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: RonConfig> Plugin for RonConfigPlugin<T> {
+    fn build(&self, app: &mut App) {
+        let path = self.path;
+
+        app.init_asset::<T>()
+            .register_asset_loader(RonConfigLoader::<T>(PhantomData))
+            .insert_resource(T::default())
+            .add_systems(
+                Startup,
+                move |asset_server: Res<AssetServer>, mut commands: Commands| {
+                    commands.insert_resource(RonConfigHandle::<T>(asset_server.load(path)));
+                },
+            )
+            .add_systems(Update, sync_config::<T>);
+    }
+}
+
+/// Keeps the config asset loaded, so that it is reloaded on changes
+#[derive(Resource)]
+struct RonConfigHandle<T: Asset>(Handle<T>);
+
+struct RonConfigLoader<T>(PhantomData<T>);
+
+impl<T: RonConfig> AssetLoader for RonConfigLoader<T> {
+    type Asset = T;
+    type Settings = ();
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ron"]
+    }
+}
+
+fn sync_config<T: RonConfig>(
+    mut asset_events: EventReader<AssetEvent<T>>,
+    assets: Res<Assets<T>>,
+    config_handle: Option<Res<RonConfigHandle<T>>>,
+    mut config: ResMut<T>,
+) {
+    let Some(config_handle) = config_handle else {
+        return;
+    };
+
+    for asset_event in asset_events.read() {
+        if let AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id } = asset_event
+        {
+            if *id != config_handle.0.id() {
+                continue;
+            }
+
+            if let Some(loaded_config) = assets.get(*id) {
+                log::info!("Applying {}", T::short_type_path());
+                *config = loaded_config.clone();
+            }
+        }
+    }
+}
@@ -1,4 +1,8 @@
-use crate::GameState;
+use crate::{
+    hostile_entity::HostileEntity,
+    walls::{NextLevelTrigger, OneWayPlatform, OutOfWorldTrigger},
+    GameState,
+};
 use bevy::prelude::*;
 use bevy::utils::HashSet;
 use bevy_rapier2d::prelude::*;
@@ -12,6 +16,56 @@ pub struct GroundSensor {
 #[derive(Clone, Default, Component)]
 pub struct GroundDetection {
     pub on_ground: bool,
+    /// Normal of the slope that is being stood on, `None` on flat ground
+    pub slope_normal: Option<Vec2>,
+    pub on_slippery_ground: bool,
+}
+
+/// Reaches further below the collider than the [`GroundSensor`], but only detects
+/// slopes. On a 45° slope only a corner of the collider touches the ground, so the
+/// ground sensor in the middle can hover above it.
+#[derive(Component)]
+pub struct SlopeSensor {
+    pub ground_detection_entity: Entity,
+    pub intersecting_slope_entities: HashSet<Entity>,
+}
+
+/// Marks ground that is not flat, i.e. slopes
+#[derive(Component, Clone, Copy)]
+pub struct GroundNormal(pub Vec2);
+
+/// Marks ground that is hard to speed up and stop on, i.e. ice
+#[derive(Component, Clone, Copy)]
+pub struct SlipperyGround;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WallSide {
+    Left,
+    Right,
+}
+
+#[derive(Component)]
+pub struct WallSensor {
+    pub wall_detection_entity: Entity,
+    pub side: WallSide,
+    pub intersecting_wall_entities: HashSet<Entity>,
+}
+
+#[derive(Clone, Default, Component)]
+pub struct WallDetection {
+    pub on_left_wall: bool,
+    pub on_right_wall: bool,
+}
+
+impl WallDetection {
+    /// The side of the wall being touched, `None` if no or both sides touch a wall
+    pub fn wall_side(&self) -> Option<WallSide> {
+        match (self.on_left_wall, self.on_right_wall) {
+            (true, false) => Some(WallSide::Left),
+            (false, true) => Some(WallSide::Right),
+            _ => None,
+        }
+    }
 }
 
 pub fn spawn_ground_sensor(
@@ -29,6 +83,12 @@ pub fn spawn_ground_sensor(
 
             let sensor_translation = Vec3::new(0., -half_extents_y, 0.);
 
+            // Deep enough to touch a 45° slope below the middle of the collider
+            let slope_depth = half_extents_x * 0.5;
+            let slope_detector_shape =
+                Collider::cuboid(half_extents_x * 0.5, 2. + slope_depth / 2.);
+            let slope_sensor_translation = Vec3::new(0., -half_extents_y - slope_depth / 2., 0.);
+
             commands.entity(entity).with_children(|builder| {
                 builder
                     .spawn_empty()
@@ -41,6 +101,56 @@ pub fn spawn_ground_sensor(
                         ground_detection_entity: entity,
                         intersecting_ground_entities: HashSet::new(),
                     });
+
+                builder
+                    .spawn_empty()
+                    .insert(ActiveEvents::COLLISION_EVENTS)
+                    .insert(slope_detector_shape)
+                    .insert(Sensor)
+                    .insert(Transform::from_translation(slope_sensor_translation))
+                    .insert(GlobalTransform::default())
+                    .insert(SlopeSensor {
+                        ground_detection_entity: entity,
+                        intersecting_slope_entities: HashSet::new(),
+                    });
+            });
+        }
+    }
+}
+
+/// Same as [`spawn_ground_sensor`] but with a sensor on both sides of the collider
+pub fn spawn_wall_sensors(
+    mut commands: Commands,
+    detect_walls_for: Query<(Entity, &Collider), Added<WallDetection>>,
+) {
+    for (entity, shape) in &detect_walls_for {
+        if let Some(cuboid) = shape.as_cuboid() {
+            let Vec2 {
+                x: half_extents_x,
+                y: half_extents_y,
+            } = cuboid.half_extents();
+
+            // Shorter than the collider, so that the floor and ceiling are not detected
+            let detector_shape = Collider::cuboid(2., half_extents_y * 0.5);
+
+            commands.entity(entity).with_children(|builder| {
+                for (side, sensor_x) in [
+                    (WallSide::Left, -half_extents_x),
+                    (WallSide::Right, half_extents_x),
+                ] {
+                    builder
+                        .spawn_empty()
+                        .insert(ActiveEvents::COLLISION_EVENTS)
+                        .insert(detector_shape.clone())
+                        .insert(Sensor)
+                        .insert(Transform::from_xyz(sensor_x, 0., 0.))
+                        .insert(GlobalTransform::default())
+                        .insert(WallSensor {
+                            wall_detection_entity: entity,
+                            side,
+                            intersecting_wall_entities: HashSet::new(),
+                        });
+                }
             });
         }
     }
@@ -79,25 +189,170 @@ fn ground_detection(
     }
 }
 
+fn slope_detection(
+    mut slope_sensors: Query<&mut SlopeSensor>,
+    mut collisions: EventReader<CollisionEvent>,
+    slopes: Query<Entity, (With<GroundNormal>, Without<Sensor>)>,
+) {
+    for collision_event in collisions.read() {
+        match collision_event {
+            CollisionEvent::Started(e1, e2, _) => {
+                if slopes.contains(*e1) {
+                    if let Ok(mut sensor) = slope_sensors.get_mut(*e2) {
+                        sensor.intersecting_slope_entities.insert(*e1);
+                    }
+                } else if slopes.contains(*e2) {
+                    if let Ok(mut sensor) = slope_sensors.get_mut(*e1) {
+                        sensor.intersecting_slope_entities.insert(*e2);
+                    }
+                }
+            }
+            CollisionEvent::Stopped(e1, e2, _) => {
+                if slopes.contains(*e1) {
+                    if let Ok(mut sensor) = slope_sensors.get_mut(*e2) {
+                        sensor.intersecting_slope_entities.remove(e1);
+                    }
+                } else if slopes.contains(*e2) {
+                    if let Ok(mut sensor) = slope_sensors.get_mut(*e1) {
+                        sensor.intersecting_slope_entities.remove(e2);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Only fixed colliders count as walls. Slopes are walked on and not slid down, one-way
+/// platforms are jumped through, and spikes and the level triggers are not walls either.
+#[allow(clippy::type_complexity)]
+fn wall_detection(
+    mut wall_sensors: Query<&mut WallSensor>,
+    mut collisions: EventReader<CollisionEvent>,
+    walls: Query<
+        &RigidBody,
+        (
+            With<Collider>,
+            Without<Sensor>,
+            Without<GroundNormal>,
+            Without<HostileEntity>,
+            Without<OutOfWorldTrigger>,
+            Without<NextLevelTrigger>,
+            Without<OneWayPlatform>,
+        ),
+    >,
+) {
+    let is_wall = |entity: &Entity| matches!(walls.get(*entity), Ok(RigidBody::Fixed));
+
+    for collision_event in collisions.read() {
+        match collision_event {
+            CollisionEvent::Started(e1, e2, _) => {
+                if is_wall(e1) {
+                    if let Ok(mut sensor) = wall_sensors.get_mut(*e2) {
+                        sensor.intersecting_wall_entities.insert(*e1);
+                    }
+                } else if is_wall(e2) {
+                    if let Ok(mut sensor) = wall_sensors.get_mut(*e1) {
+                        sensor.intersecting_wall_entities.insert(*e2);
+                    }
+                }
+            }
+            CollisionEvent::Stopped(e1, e2, _) => {
+                if is_wall(e1) {
+                    if let Ok(mut sensor) = wall_sensors.get_mut(*e2) {
+                        sensor.intersecting_wall_entities.remove(e1);
+                    }
+                } else if is_wall(e2) {
+                    if let Ok(mut sensor) = wall_sensors.get_mut(*e1) {
+                        sensor.intersecting_wall_entities.remove(e2);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Runs every frame instead of only when a sensor changes, as both the ground and the
+/// slope sensor decide whether the player is on the ground, and one-way platforms
+/// switch between solid and passable while the sensor touches them
 fn update_on_ground(
     mut ground_detectors: Query<&mut GroundDetection>,
-    ground_sensors: Query<&GroundSensor, Changed<GroundSensor>>,
+    ground_sensors: Query<&GroundSensor>,
+    slope_sensors: Query<&SlopeSensor>,
+    ground_normals: Query<&GroundNormal>,
+    slippery_grounds: Query<(), With<SlipperyGround>>,
+    solver_groups: Query<&SolverGroups>,
 ) {
+    // Passable one-way platforms have no solver groups
+    let is_solid = |entity: &Entity| {
+        solver_groups
+            .get(*entity)
+            .map_or(true, |groups| groups.filters != Group::NONE)
+    };
+
     for sensor in &ground_sensors {
         if let Ok(mut ground_detection) = ground_detectors.get_mut(sensor.ground_detection_entity) {
-            ground_detection.on_ground = !sensor.intersecting_ground_entities.is_empty();
+            ground_detection.on_ground = sensor.intersecting_ground_entities.iter().any(is_solid);
+            ground_detection.slope_normal = sensor
+                .intersecting_ground_entities
+                .iter()
+                .find_map(|entity| ground_normals.get(*entity).ok())
+                .map(|ground_normal| ground_normal.0);
+            ground_detection.on_slippery_ground = sensor
+                .intersecting_ground_entities
+                .iter()
+                .any(|entity| slippery_grounds.contains(*entity));
+        }
+    }
+
+    // Slopes below the ground sensor still count as ground
+    for sensor in &slope_sensors {
+        let Some(ground_normal) = sensor
+            .intersecting_slope_entities
+            .iter()
+            .find_map(|entity| ground_normals.get(*entity).ok())
+        else {
+            continue;
+        };
+
+        if let Ok(mut ground_detection) = ground_detectors.get_mut(sensor.ground_detection_entity) {
+            ground_detection.on_ground = true;
+            ground_detection.slope_normal.get_or_insert(ground_normal.0);
+        }
+    }
+}
+
+fn update_on_wall(
+    mut wall_detectors: Query<&mut WallDetection>,
+    wall_sensors: Query<&WallSensor, Changed<WallSensor>>,
+) {
+    for sensor in &wall_sensors {
+        if let Ok(mut wall_detection) = wall_detectors.get_mut(sensor.wall_detection_entity) {
+            let on_wall = !sensor.intersecting_wall_entities.is_empty();
+
+            match sensor.side {
+                WallSide::Left => wall_detection.on_left_wall = on_wall,
+                WallSide::Right => wall_detection.on_right_wall = on_wall,
+            }
         }
     }
 }
 
-/// Handles platformer-specific physics operations, specifically ground detection.
+/// Handles platformer-specific physics operations, specifically ground and wall detection.
 pub struct GroundDetectionPlugin;
 
 impl Plugin for GroundDetectionPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
             Update,
-            (spawn_ground_sensor, ground_detection, update_on_ground)
+            (
+                spawn_ground_sensor,
+                ground_detection,
+                slope_detection,
+                update_on_ground,
+                spawn_wall_sensors,
+                wall_detection,
+                update_on_wall,
+            )
                 .run_if(in_state(GameState::PlayingScreen)),
         );
     }
@@ -1,217 +1,564 @@
 use crate::{
-    assets::AudioAssets,
+    assets::{AssetsLoadingState, AudioAssets},
+    chase::Chase,
     colliders::ColliderBundle,
-    player::{Blinded, ContinueTakingDamage, HealthBar, PlayerEntity},
+    config::RonConfigPlugin,
+    moving_platform::PathMode,
+    player::{
+        move_towards, Blinded, DashState, HealthBar, HostileContacts, Invulnerable, JumpState,
+        PlayerEntity, PlayerMovementConfig,
+    },
+    projectile::Caster,
     sprite_animation::Animation,
-    utils::Maybe,
+    stomp::StompImmune,
+    utils::{maybe_enum_field, maybe_float_field, maybe_floats_field, maybe_int_field, Maybe},
     GameState, GRID_SIZE,
 };
 use bevy::prelude::*;
+use bevy::utils::HashMap;
 use bevy_ecs_ldtk::{prelude::*, utils::ldtk_pixel_coords_to_translation_pivoted};
-use bevy_rapier2d::prelude::{ActiveEvents, CollisionEvent, Velocity};
+use bevy_rapier2d::prelude::{
+    ActiveEvents, Collider, CollisionEvent, LockedAxes, RigidBody, Velocity,
+};
+use serde::Deserialize;
+use std::collections::BTreeMap;
 
 pub struct HostilePlugin;
 
 impl Plugin for HostilePlugin {
     fn build(&self, app: &mut App) {
-        app.register_ldtk_entity::<Hostile>("Sand_Ghoul")
-            .register_ldtk_entity::<Hostile>("Grave_Revenant")
-            .register_ldtk_entity::<Hostile>("Mutilated_Stumbler")
-            .register_ldtk_entity::<Hostile>("Adept_Necromancer")
-            .add_systems(
-                Update,
-                (patrol, damage_player).run_if(in_state(GameState::PlayingScreen)),
+        app.add_plugins(RonConfigPlugin::<HostilesConfig>::new(
+            "config/hostiles.ron",
+        ))
+        .insert_resource(HostileLayouts::default())
+        .add_systems(
+            Update,
+            spawn_hostiles.run_if(in_state(AssetsLoadingState::Loaded)),
+        )
+        .add_systems(
+            Update,
+            (
+                (patrol, turn_hostiles).chain(),
+                (track_hostile_contacts, damage_player).chain(),
+                blinding_power,
             )
-            .add_systems(
-                FixedUpdate,
-                blinding_power.run_if(in_state(GameState::PlayingScreen)),
-            );
+                .run_if(in_state(GameState::PlayingScreen)),
+        );
     }
 }
 
-#[derive(better_default::Default, Bundle, LdtkEntity)]
-#[default(
-    active_events: ActiveEvents::COLLISION_EVENTS,
-    animation: Animation::new(0, 3, Timer::from_seconds(0.25, TimerMode::Repeating))
-)]
+/// Every kind of hostile entity, loaded from `assets/config/hostiles.ron`.
+///
+/// LDtk entities aren't registered one by one, every entity whose identifier is a
+/// key of `hostiles` is turned into a [`Hostile`] by [`spawn_hostiles`].
+#[derive(Asset, TypePath, Resource, Deserialize, Debug, Clone, Default)]
+pub struct HostilesConfig {
+    /// LDtk entity identifier -> Definition
+    pub hostiles: BTreeMap<String, HostileDefinition>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct HostileDefinition {
+    /// Hearts taken from the player on contact
+    pub damage: u8,
+    /// Pixels
+    pub collider_size: Vec2,
+    /// Patrol speed in pixels per second
+    pub speed: f32,
+    pub sprite: HostileSprite,
+    /// Flying hostiles chase the player through the air, the others stay on the floor
+    #[serde(default)]
+    pub flying: bool,
+    #[serde(default)]
+    pub abilities: Vec<HostileAbility>,
+}
+
+/// Single row sprite sheet that is played as the walking animation
+#[derive(Deserialize, Debug, Clone)]
+pub struct HostileSprite {
+    /// Relative to `assets/`
+    pub path: String,
+    /// Pixels
+    pub tile_size: UVec2,
+    pub frames: usize,
+    /// Seconds per frame
+    pub frame_time: f32,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub enum HostileAbility {
+    /// Blinds the player for `duration` seconds when it comes within `radius` tiles
+    Blindness { radius: f32, duration: f32 },
+    /// Can't be defeated by landing on it, see [`crate::stomp`]
+    StompImmune,
+}
+
+#[derive(Bundle)]
 pub struct Hostile {
-    #[sprite_sheet]
     pub sprite_sheet: Sprite,
-    #[from_entity_instance]
     pub collider_bundle: ColliderBundle,
-    #[ldtk_entity]
     pub patrol: Patrol,
     pub active_events: ActiveEvents,
     hostile_entity: HostileEntity,
     pub animation: Animation,
-    #[from_entity_instance]
     pub damage_count: DamageCount,
-    #[from_entity_instance]
     pub blindness_power: Maybe<BlindnessPower>,
+    pub chase: Maybe<Chase>,
+    pub caster: Maybe<Caster>,
+    pub stomp_immune: Maybe<StompImmune>,
+    pub facing: Facing,
 }
 
 #[derive(Default, Component)]
 pub struct HostileEntity;
 
 #[derive(Default, Component)]
-pub struct BlindnessPower;
-
-impl From<&EntityInstance> for Maybe<BlindnessPower> {
-    fn from(value: &EntityInstance) -> Self {
-        if value.identifier == "Adept_Necromancer" {
-            return Self::new(BlindnessPower);
-        }
-        Self::NONE
-    }
+pub struct BlindnessPower {
+    /// Pixels
+    pub radius: f32,
+    /// Seconds
+    pub duration: f32,
 }
 
 #[derive(Default, Component)]
 pub struct DamageCount(pub u8);
 
-impl From<&EntityInstance> for DamageCount {
-    fn from(value: &EntityInstance) -> Self {
-        match value.identifier.as_str() {
-            "Sand_Ghoul" | "Adept_Necromancer" => DamageCount(1),
-            "Grave_Revenant" => DamageCount(2),
-            "Mutilated_Stumbler" => DamageCount(3),
-            _ => DamageCount(0),
-        }
-    }
-}
+/// Seconds a hostile takes to turn around
+const TURN_TIME: f32 = 0.2;
 
+/// Route of a hostile, set through the LDtk `patrol`, `mode` and `wait_times` fields
 #[derive(Component, Debug, Default)]
 pub struct Patrol {
     pub points: Vec<Vec2>,
+    /// The point that is being moved towards
     pub index: usize,
     pub forward: bool,
+    pub mode: PathMode,
+    /// Pixels per second
+    pub speed: f32,
+    /// Seconds to stand still at each point, indexed like `points`
+    pub wait_times: Vec<f32>,
+    pub wait_time_left: f32,
+    /// Set once a [`PathMode::OneShot`] patrol reached its last point
+    pub finished: bool,
 }
 
-impl LdtkEntity for Patrol {
-    fn bundle_entity(
-        entity_instance: &EntityInstance,
-        layer_instance: &LayerInstance,
-        _tileset: Option<&Handle<Image>>,
-        _tileset_definition: Option<&TilesetDefinition>,
-        _asset_server: &AssetServer,
-        _texture_atlases: &mut Assets<TextureAtlasLayout>,
-    ) -> Self {
-        let mut points = Vec::new();
+impl Patrol {
+    /// Restarts the patrol from the first point, which is returned
+    pub fn reset(&mut self) -> Vec2 {
+        self.index = 1;
+        self.forward = true;
+        self.wait_time_left = 0.;
+        self.finished = false;
+        self.points.first().copied().unwrap_or_default()
+    }
+
+    /// Where the current leg of the patrol started, or the last point of a finished patrol
+    pub fn leg_start(&self) -> Option<Vec2> {
+        if self.finished || self.points.is_empty() {
+            return self.points.get(self.index).copied();
+        }
+
+        let index = match self.mode {
+            PathMode::Loop => (self.index + self.points.len() - 1) % self.points.len(),
+            _ if self.forward => self.index.checked_sub(1)?,
+            _ => self.index + 1,
+        };
+        self.points.get(index).copied()
+    }
+
+    fn advance(&mut self) {
+        match self
+            .mode
+            .next_index(self.index, &mut self.forward, self.points.len())
+        {
+            Some(index) => self.index = index,
+            None => self.finished = true,
+        }
+    }
+}
+
+/// Which way a hostile looks, it turns towards where it moves by squeezing the sprite
+/// instead of flipping it at once
+#[derive(Component, Debug)]
+pub struct Facing {
+    /// 1 is right and -1 is left, values in between are mid turn
+    pub direction: f32,
+    /// Size of the sprite while not turning
+    pub sprite_size: Vec2,
+}
+
+/// Translations of the entity followed by every point of its LDtk `patrol` field
+pub fn patrol_points(
+    entity_instance: &EntityInstance,
+    layer_metadata: &LayerMetadata,
+) -> Vec<Vec2> {
+    let mut points = Vec::new();
+    points.push(ldtk_pixel_coords_to_translation_pivoted(
+        entity_instance.px,
+        layer_metadata.c_hei * layer_metadata.grid_size,
+        IVec2::new(entity_instance.width, entity_instance.height),
+        entity_instance.pivot,
+    ));
+
+    let ldtk_patrol_points = entity_instance
+        .iter_points_field("patrol")
+        .expect("patrol field should be correctly typed");
+
+    for ldtk_point in ldtk_patrol_points {
+        let pixel_coords = (ldtk_point.as_vec2() + Vec2::new(0.5, 0.5))
+            * Vec2::splat(layer_metadata.grid_size as f32);
+
         points.push(ldtk_pixel_coords_to_translation_pivoted(
-            entity_instance.px,
-            layer_instance.c_hei * layer_instance.grid_size,
+            pixel_coords.as_ivec2(),
+            layer_metadata.c_hei * layer_metadata.grid_size,
             IVec2::new(entity_instance.width, entity_instance.height),
             entity_instance.pivot,
         ));
+    }
 
-        let ldtk_patrol_points = entity_instance
-            .iter_points_field("patrol")
-            .expect("patrol field should be correctrly typed for hostile entities");
+    points
+}
 
-        for ldtk_point in ldtk_patrol_points {
-            let pixel_coords = (ldtk_point.as_vec2() + Vec2::new(0.5, 0.5))
-                * Vec2::splat(layer_instance.grid_size as f32);
+/// Atlas layouts of the hostile sprite sheets, hostiles with the same sheet size share one
+#[derive(Resource, Default)]
+pub struct HostileLayouts(HashMap<(UVec2, usize), Handle<TextureAtlasLayout>>);
 
-            points.push(ldtk_pixel_coords_to_translation_pivoted(
-                pixel_coords.as_ivec2(),
-                layer_instance.c_hei * layer_instance.grid_size,
-                IVec2::new(entity_instance.width, entity_instance.height),
-                entity_instance.pivot,
-            ));
+impl HostileLayouts {
+    pub fn get_or_add(
+        &mut self,
+        sprite: &HostileSprite,
+        texture_atlas_layouts: &mut Assets<TextureAtlasLayout>,
+    ) -> Handle<TextureAtlasLayout> {
+        self.0
+            .entry((sprite.tile_size, sprite.frames))
+            .or_insert_with(|| {
+                texture_atlas_layouts.add(TextureAtlasLayout::from_grid(
+                    sprite.tile_size,
+                    sprite.frames as u32,
+                    1,
+                    None,
+                    None,
+                ))
+            })
+            .clone()
+    }
+}
+
+/// Turns LDtk entities that are described in [`HostilesConfig`] into [`Hostile`]s
+fn spawn_hostiles(
+    query: Query<(Entity, &EntityInstance, &Parent), Added<EntityInstance>>,
+    layer_query: Query<&LayerMetadata>,
+    hostiles_config: Res<HostilesConfig>,
+    asset_server: Res<AssetServer>,
+    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut layouts: ResMut<HostileLayouts>,
+    mut commands: Commands,
+) {
+    for (entity, entity_instance, parent) in &query {
+        let Some(definition) = hostiles_config.hostiles.get(&entity_instance.identifier) else {
+            continue;
+        };
+
+        let Ok(layer_metadata) = layer_query.get(parent.get()) else {
+            log::error!(
+                "Hostile {} should be spawned in an entity layer",
+                entity_instance.identifier
+            );
+            continue;
+        };
+
+        let layout = layouts.get_or_add(&definition.sprite, &mut texture_atlas_layouts);
+        commands.entity(entity).insert(Hostile::new(
+            definition,
+            entity_instance,
+            layer_metadata,
+            asset_server.load(&definition.sprite.path),
+            layout,
+        ));
+    }
+}
+
+impl Hostile {
+    /// Hostile described by `definition` that patrols along the `patrol` field of
+    /// `entity_instance`.
+    ///
+    /// The optional LDtk fields `speed`, `damage`, `blind_radius` (in tiles) and
+    /// `blind_duration` override the definition for that one hostile.
+    pub fn new(
+        definition: &HostileDefinition,
+        entity_instance: &EntityInstance,
+        layer_metadata: &LayerMetadata,
+        image: Handle<Image>,
+        layout: Handle<TextureAtlasLayout>,
+    ) -> Self {
+        let sprite = &definition.sprite;
+
+        let speed = maybe_float_field(entity_instance, "speed").unwrap_or(definition.speed);
+        let damage =
+            maybe_int_field(entity_instance, "damage").map_or(definition.damage, |damage| {
+                u8::try_from(damage).unwrap_or_else(|_| {
+                    panic!(
+                        "Field `damage` of {} {} should be between 0 and {}",
+                        entity_instance.identifier,
+                        entity_instance.iid,
+                        u8::MAX
+                    )
+                })
+            });
+        let mode = maybe_enum_field(entity_instance, "mode")
+            .map_or_else(PathMode::default, PathMode::from);
+        let wait_times = maybe_floats_field(entity_instance, "wait_times")
+            .iter()
+            .map(|wait_time| wait_time.unwrap_or_default())
+            .collect();
+        let blind_radius = maybe_float_field(entity_instance, "blind_radius");
+        let blind_duration = maybe_float_field(entity_instance, "blind_duration");
+
+        let mut chase = Maybe::<Chase>::from(entity_instance);
+        if let Some(chase) = &mut chase.0 {
+            chase.flying = definition.flying;
+        }
+
+        let mut blindness_power = Maybe::NONE;
+        let mut stomp_immune = Maybe::NONE;
+
+        for ability in &definition.abilities {
+            match *ability {
+                HostileAbility::Blindness { radius, duration } => {
+                    blindness_power = Maybe::new(BlindnessPower {
+                        radius: blind_radius.unwrap_or(radius) * GRID_SIZE as f32,
+                        duration: blind_duration.unwrap_or(duration),
+                    });
+                }
+                HostileAbility::StompImmune => stomp_immune = Maybe::new(StompImmune),
+            }
+        }
+
+        if blindness_power.0.is_none() && (blind_radius.is_some() || blind_duration.is_some()) {
+            log::warn!(
+                "{} {} has blind fields but no Blindness ability in hostiles.ron",
+                entity_instance.identifier,
+                entity_instance.iid
+            );
         }
 
         Self {
-            points,
-            index: 1,
-            forward: true,
+            sprite_sheet: Sprite::from_atlas_image(image, TextureAtlas { layout, index: 0 }),
+            collider_bundle: ColliderBundle {
+                rigid_body: Maybe::new(RigidBody::KinematicVelocityBased),
+                collider: Collider::cuboid(
+                    definition.collider_size.x / 2.,
+                    definition.collider_size.y / 2.,
+                ),
+                rotation_constraints: LockedAxes::ROTATION_LOCKED,
+                velocity: Maybe::new(Velocity::default()),
+                ..default()
+            },
+            patrol: Patrol {
+                points: patrol_points(entity_instance, layer_metadata),
+                index: 1,
+                forward: true,
+                mode,
+                speed,
+                wait_times,
+                ..default()
+            },
+            active_events: ActiveEvents::COLLISION_EVENTS,
+            hostile_entity: HostileEntity,
+            animation: Animation::new(
+                0,
+                sprite.frames - 1,
+                Timer::from_seconds(sprite.frame_time, TimerMode::Repeating),
+            ),
+            damage_count: DamageCount(damage),
+            blindness_power,
+            chase,
+            caster: Maybe::<Caster>::from(entity_instance),
+            stomp_immune,
+            facing: Facing {
+                direction: 1.,
+                sprite_size: sprite.tile_size.as_vec2(),
+            },
         }
     }
 }
 
-pub fn patrol(mut query: Query<(&mut Transform, &mut Velocity, &mut Patrol, &mut Sprite)>) {
-    for (mut transform, mut velocity, mut patrol, mut sprite) in &mut query {
-        if patrol.points.len() <= 1 {
+pub fn patrol(
+    mut query: Query<(&Transform, &mut Velocity, &mut Patrol, Option<&Chase>)>,
+    time: Res<Time>,
+) {
+    let delta = time.delta_secs();
+
+    for (transform, mut velocity, mut patrol, chase) in &mut query {
+        // Chasing hostiles are moved by `chase::chase_player`
+        if chase.is_some_and(|chase| !chase.is_patrolling()) {
             continue;
         }
 
-        let mut new_velocity =
-            (patrol.points[patrol.index] - transform.translation.truncate()).normalize() * 45.;
-
-        if new_velocity.dot(velocity.linvel) < 0. {
-            if patrol.index == 0 {
-                patrol.forward = true;
-                sprite.flip_x = false;
-            } else if patrol.index == patrol.points.len() - 1 {
-                patrol.forward = false;
-                sprite.flip_x = true;
-            }
+        if patrol.points.len() <= 1 || patrol.finished || delta == 0. {
+            velocity.linvel = Vec2::ZERO;
+            continue;
+        }
 
-            transform.translation.x = patrol.points[patrol.index].x;
-            transform.translation.y = patrol.points[patrol.index].y;
+        if patrol.wait_time_left > 0. {
+            patrol.wait_time_left -= delta;
+            velocity.linvel = Vec2::ZERO;
+            continue;
+        }
 
-            if patrol.forward {
-                patrol.index += 1;
-            } else {
-                patrol.index -= 1;
-            }
+        let to_target = patrol.points[patrol.index] - transform.translation.truncate();
 
-            new_velocity =
-                (patrol.points[patrol.index] - transform.translation.truncate()).normalize() * 75.;
+        if to_target.length() <= patrol.speed * delta {
+            // Land exactly on the point instead of overshooting it
+            velocity.linvel = to_target / delta;
+            patrol.wait_time_left = patrol
+                .wait_times
+                .get(patrol.index)
+                .copied()
+                .unwrap_or_default();
+            patrol.advance();
+        } else {
+            velocity.linvel = to_target.normalize() * patrol.speed;
         }
+    }
+}
+
+/// Turns hostiles towards where they move, standing still or moving straight up and
+/// down keeps the direction they face
+fn turn_hostiles(mut query: Query<(&Velocity, &mut Facing, &mut Sprite)>, time: Res<Time>) {
+    for (velocity, mut facing, mut sprite) in &mut query {
+        let target = if velocity.linvel.x > 0.1 {
+            1.
+        } else if velocity.linvel.x < -0.1 {
+            -1.
+        } else {
+            facing.direction.signum()
+        };
 
-        velocity.linvel = new_velocity;
+        // Turning around goes from 1 to -1
+        facing.direction =
+            move_towards(facing.direction, target, 2. / TURN_TIME * time.delta_secs());
+
+        sprite.flip_x = facing.direction < 0.;
+        // Only the sprite is squeezed, the collider keeps its size
+        sprite.custom_size = Some(Vec2::new(
+            facing.sprite_size.x * facing.direction.abs().max(0.1),
+            facing.sprite_size.y,
+        ));
     }
 }
 
-fn damage_player(
+pub fn track_hostile_contacts(
     mut collision_events: EventReader<CollisionEvent>,
-    mut player_query: Query<(Entity, &mut HealthBar, Option<&AudioPlayer>), With<PlayerEntity>>,
-    hostile_query: Query<(Entity, &DamageCount), With<HostileEntity>>,
+    mut player_query: Query<(Entity, &mut HostileContacts), With<PlayerEntity>>,
+    hostile_query: Query<Entity, With<HostileEntity>>,
+) {
+    let Ok((player_entity, mut hostile_contacts)) = player_query.get_single_mut() else {
+        return;
+    };
+
+    for collision in collision_events.read() {
+        let (entity_one, entity_two, started) = match *collision {
+            CollisionEvent::Started(entity_one, entity_two, ..) => (entity_one, entity_two, true),
+            CollisionEvent::Stopped(entity_one, entity_two, ..) => (entity_one, entity_two, false),
+        };
+
+        let other_entity = if entity_one == player_entity {
+            entity_two
+        } else if entity_two == player_entity {
+            entity_one
+        } else {
+            continue;
+        };
+
+        if !hostile_query.contains(other_entity) {
+            continue;
+        }
+
+        if started {
+            hostile_contacts.0.insert(other_entity);
+        } else {
+            hostile_contacts.0.remove(&other_entity);
+        }
+    }
+}
+
+/// Damages the player once per contact, afterwards the player is knocked back and
+/// [`Invulnerable`] for a while. Staying in contact after that hurts again.
+#[allow(clippy::type_complexity)]
+pub fn damage_player(
+    mut player_query: Query<
+        (
+            Entity,
+            &Transform,
+            &mut Velocity,
+            &mut HealthBar,
+            &mut HostileContacts,
+            &mut JumpState,
+            &mut DashState,
+            Option<&AudioPlayer>,
+        ),
+        (With<PlayerEntity>, Without<Invulnerable>),
+    >,
+    hostile_query: Query<(&GlobalTransform, &DamageCount), With<HostileEntity>>,
+    movement_config: Res<PlayerMovementConfig>,
     mut commands: Commands,
     audio_assets: Res<AudioAssets>,
 ) {
-    for collision in collision_events.read() {
-        let (player_entity, mut player_healtbar, audio_player) = player_query.single_mut();
-        match *collision {
-            CollisionEvent::Started(entity_one, entity_two, ..) => {
-                if entity_one == player_entity || entity_two == player_entity {
-                    for (hostile_entity, damage_count) in &hostile_query {
-                        if entity_two == hostile_entity || entity_one == hostile_entity {
-                            log::info!("Got damage from hostile entity");
-
-                            if player_healtbar.health > damage_count.0 {
-                                player_healtbar.health -= damage_count.0;
-
-                                let mut entity_commands = commands.entity(player_entity);
-                                entity_commands.insert(ContinueTakingDamage(damage_count.0));
-
-                                if audio_player.is_none() {
-                                    entity_commands.insert((
-                                        AudioPlayer(audio_assets.damage.clone()),
-                                        PlaybackSettings::REMOVE,
-                                    ));
-                                }
-                            } else {
-                                player_healtbar.health = 0;
-                            }
-
-                            return;
-                        }
-                    }
-                }
-            }
-            CollisionEvent::Stopped(entity_one, entity_two, ..) => {
-                if entity_one == player_entity || entity_two == player_entity {
-                    for (hostile_entity, ..) in &hostile_query {
-                        if entity_two == hostile_entity || entity_one == hostile_entity {
-                            log::info!("Removing Continuous Damage");
-                            commands
-                                .entity(player_entity)
-                                .remove::<ContinueTakingDamage>();
-                            return;
-                        }
-                    }
-                }
-            }
+    for (
+        player_entity,
+        player_transform,
+        mut velocity,
+        mut player_healthbar,
+        mut hostile_contacts,
+        mut jump_state,
+        mut dash_state,
+        audio_player,
+    ) in &mut player_query
+    {
+        // Hostile entities of the previous level are gone without a `Stopped` event
+        hostile_contacts
+            .0
+            .retain(|entity| hostile_query.contains(*entity));
+
+        let Some((hostile_transform, damage_count)) = hostile_contacts
+            .0
+            .iter()
+            .filter_map(|entity| hostile_query.get(*entity).ok())
+            .max_by_key(|(_, damage_count)| damage_count.0)
+        else {
+            continue;
+        };
+
+        log::info!("Got damage from hostile entity");
+        player_healthbar.health = player_healthbar.health.saturating_sub(damage_count.0);
+
+        // Knock the player away from the hostile entity
+        let direction = if player_transform.translation.x < hostile_transform.translation().x {
+            -1.
+        } else {
+            1.
+        };
+        velocity.linvel = Vec2::new(
+            direction * movement_config.knockback_horizontal_velocity,
+            movement_config.knockback_vertical_velocity,
+        );
+        jump_state.is_jumping = false;
+        jump_state.control_lock = movement_config.knockback_control_lock;
+        dash_state.time_left = 0.;
+
+        let mut entity_commands = commands.entity(player_entity);
+        entity_commands.insert(Invulnerable(Timer::from_seconds(
+            movement_config.invulnerability_time,
+            TimerMode::Once,
+        )));
+
+        if audio_player.is_none() {
+            entity_commands.insert((
+                AudioPlayer(audio_assets.damage.clone()),
+                PlaybackSettings::REMOVE,
+            ));
         }
     }
 }
@@ -219,21 +566,22 @@ fn damage_player(
 #[allow(clippy::type_complexity)]
 fn blinding_power(
     player_query: Query<(Entity, &Transform), (Without<Blinded>, With<PlayerEntity>)>,
-    blinding_power_entity: Query<&Transform, (With<BlindnessPower>, With<HostileEntity>)>,
+    blinding_power_entity: Query<(&Transform, &BlindnessPower), With<HostileEntity>>,
     mut commands: Commands,
 ) {
     for (entity, player_transform) in &player_query {
-        for blinding_power_transform in &blinding_power_entity {
+        for (blinding_power_transform, blindness_power) in &blinding_power_entity {
             if player_transform
                 .translation
                 .truncate()
                 .distance(blinding_power_transform.translation.truncate())
-                <= (GRID_SIZE as f32 * 10.)
+                <= blindness_power.radius
             {
                 log::info!("Detected Player, Effecting with Blindness");
-                commands
-                    .entity(entity)
-                    .insert(Blinded(Timer::from_seconds(15., TimerMode::Once)));
+                commands.entity(entity).insert(Blinded(Timer::from_seconds(
+                    blindness_power.duration,
+                    TimerMode::Once,
+                )));
             }
         }
     }
@@ -0,0 +1,187 @@
+use crate::{
+    assets::AssetsLoadingState,
+    checkpoint::RespawnAtCheckpointEvent,
+    hostile_entity::{Hostile, HostileEntity, HostileLayouts, HostilesConfig},
+    level_manager::CurrentLevelInfo,
+    screens::game_over_screen::RestartGameEvent,
+    time::TimeTakenRes,
+    GameState, GRID_SIZE,
+};
+use bevy::prelude::*;
+use bevy::utils::Duration;
+use bevy_ecs_ldtk::prelude::*;
+use bevy_light_2d::prelude::PointLight2d;
+
+/// `Hostile_Spawner` LDtk entities keep spawning hostiles while the level is played,
+/// see [`HostileSpawner`]
+pub struct HostileSpawnerPlugin;
+
+impl Plugin for HostileSpawnerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            setup_spawners.run_if(in_state(AssetsLoadingState::Loaded)),
+        )
+        .add_systems(
+            Update,
+            spawn_from_spawners.run_if(in_state(GameState::PlayingScreen)),
+        )
+        .add_systems(Update, reset_spawners);
+    }
+}
+
+/// Set through the `hostile`, `interval` and `max_alive` LDtk fields. The spawned
+/// hostiles start at the spawner and patrol along its `patrol` field.
+#[derive(Component, Debug)]
+pub struct HostileSpawner {
+    /// Key of [`HostilesConfig::hostiles`]
+    pub hostile: String,
+    /// Time between spawns, measured on [`TimeTakenRes::stopwatch`]
+    pub interval: Duration,
+    /// Hostiles of this spawner that may be alive at once
+    pub max_alive: usize,
+    /// Stopwatch time of the next spawn, `None` until the spawner is first updated
+    pub next_spawn: Option<Duration>,
+}
+
+/// Hostile that was spawned by the [`HostileSpawner`] entity
+#[derive(Component)]
+pub struct SpawnedBy(pub Entity);
+
+fn setup_spawners(
+    query: Query<(Entity, &EntityInstance), Added<EntityInstance>>,
+    hostiles_config: Res<HostilesConfig>,
+    mut commands: Commands,
+) {
+    for (entity, entity_instance) in &query {
+        if entity_instance.identifier != "Hostile_Spawner" {
+            continue;
+        }
+
+        let hostile = entity_instance
+            .get_string_field("hostile")
+            .expect("hostile field should be correctly typed");
+        let interval = *entity_instance
+            .get_float_field("interval")
+            .expect("interval field should be correctly typed");
+        let max_alive = *entity_instance
+            .get_int_field("max_alive")
+            .expect("max_alive field should be correctly typed");
+
+        if !hostiles_config.hostiles.contains_key(hostile) {
+            log::error!(
+                "Hostile_Spawner {} spawns {}, which isn't in hostiles.ron",
+                entity_instance.iid,
+                hostile
+            );
+            continue;
+        }
+
+        commands.entity(entity).insert((
+            HostileSpawner {
+                hostile: hostile.clone(),
+                interval: Duration::from_secs_f32(interval),
+                max_alive: max_alive.max(0) as usize,
+                next_spawn: None,
+            },
+            PointLight2d {
+                // hsl(0, 70%, 50%)
+                color: Color::hsl(0., 0.7, 0.5),
+                intensity: 0.5,
+                radius: GRID_SIZE as f32 * 2.,
+                ..default()
+            },
+        ));
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn spawn_from_spawners(
+    mut spawner_query: Query<(
+        Entity,
+        &EntityInstance,
+        &Transform,
+        &Parent,
+        &mut HostileSpawner,
+    )>,
+    spawned_query: Query<&SpawnedBy, With<HostileEntity>>,
+    layer_query: Query<&LayerMetadata>,
+    time_taken_res: Res<TimeTakenRes>,
+    hostiles_config: Res<HostilesConfig>,
+    asset_server: Res<AssetServer>,
+    mut texture_atlas_layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut layouts: ResMut<HostileLayouts>,
+    mut commands: Commands,
+) {
+    let elapsed = time_taken_res.stopwatch.elapsed();
+
+    for (spawner_entity, entity_instance, transform, parent, mut spawner) in &mut spawner_query {
+        let interval = spawner.interval;
+        let next_spawn = match spawner.next_spawn {
+            Some(next_spawn) if next_spawn <= elapsed + interval => next_spawn,
+            // Not started yet, or the stopwatch went back because the level was restarted
+            _ => *spawner.next_spawn.insert(elapsed + interval),
+        };
+        if elapsed < next_spawn {
+            continue;
+        }
+        // A full spawner waits a whole interval again, so defeated hostiles aren't
+        // replaced right away
+        spawner.next_spawn = Some(elapsed + interval);
+
+        let alive = spawned_query
+            .iter()
+            .filter(|spawned_by| spawned_by.0 == spawner_entity)
+            .count();
+        if alive >= spawner.max_alive {
+            continue;
+        }
+
+        let (Some(definition), Ok(layer_metadata)) = (
+            hostiles_config.hostiles.get(&spawner.hostile),
+            layer_query.get(parent.get()),
+        ) else {
+            continue;
+        };
+
+        log::info!("Spawning {} from Hostile_Spawner", spawner.hostile);
+        let layout = layouts.get_or_add(&definition.sprite, &mut texture_atlas_layouts);
+        // Siblings of the spawner, so that the patrol points are in the same space
+        commands
+            .spawn((
+                Hostile::new(
+                    definition,
+                    entity_instance,
+                    layer_metadata,
+                    asset_server.load(&definition.sprite.path),
+                    layout,
+                ),
+                *transform,
+                SpawnedBy(spawner_entity),
+            ))
+            .set_parent(parent.get());
+    }
+}
+
+/// Spawned hostiles don't carry over to a restarted or new level, and the spawners wait
+/// a whole interval again
+fn reset_spawners(
+    current_level_info: Res<CurrentLevelInfo>,
+    mut restart_game_events: EventReader<RestartGameEvent>,
+    mut respawn_events: EventReader<RespawnAtCheckpointEvent>,
+    mut spawner_query: Query<&mut HostileSpawner>,
+    spawned_query: Query<Entity, With<SpawnedBy>>,
+    mut commands: Commands,
+) {
+    let restarted = restart_game_events.read().count() > 0 || respawn_events.read().count() > 0;
+
+    if current_level_info.is_changed() || restarted {
+        for spawned in &spawned_query {
+            commands.entity(spawned).despawn_recursive();
+        }
+
+        for mut spawner in &mut spawner_query {
+            spawner.next_spawn = None;
+        }
+    }
+}
@@ -0,0 +1,229 @@
+use crate::{
+    save::{self, SaveData},
+    touch_controls::TouchActions,
+};
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Translates raw input into [`InputAction`]s, gameplay systems should read
+/// `Res<ButtonInput<InputAction>>` instead of `Res<ButtonInput<KeyCode>>`
+pub struct ActionInputPlugin;
+
+impl Plugin for ActionInputPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ButtonInput::<InputAction>::default())
+            .insert_resource(load_bindings())
+            .insert_resource(Rebinding::default())
+            .add_systems(
+                PreUpdate,
+                (update_action_input.in_set(ActionInputSet), rebind_action)
+                    .chain()
+                    .after(InputSystem),
+            );
+    }
+}
+
+/// [`ButtonInput<InputAction>`] is updated in this set, sources of actions other than
+/// keyboard and gamepad must run before it
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ActionInputSet;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum InputAction {
+    MoveLeft,
+    MoveRight,
+    Jump,
+    Dash,
+    Pause,
+}
+
+impl InputAction {
+    pub const ALL: [InputAction; 5] = [
+        InputAction::MoveLeft,
+        InputAction::MoveRight,
+        InputAction::Jump,
+        InputAction::Dash,
+        InputAction::Pause,
+    ];
+
+    /// Gamepad buttons are not rebindable, the left stick also triggers [`InputAction::MoveLeft`]
+    /// and [`InputAction::MoveRight`]
+    pub fn gamepad_button(self) -> GamepadButton {
+        match self {
+            InputAction::MoveLeft => GamepadButton::DPadLeft,
+            InputAction::MoveRight => GamepadButton::DPadRight,
+            InputAction::Jump => GamepadButton::South,
+            InputAction::Dash => GamepadButton::West,
+            InputAction::Pause => GamepadButton::Start,
+        }
+    }
+
+    fn is_gamepad_active(self, gamepad: &Gamepad) -> bool {
+        let stick_x = gamepad.left_stick().x;
+
+        gamepad.pressed(self.gamepad_button())
+            || match self {
+                InputAction::MoveLeft => stick_x < -STICK_DEADZONE,
+                InputAction::MoveRight => stick_x > STICK_DEADZONE,
+                _ => false,
+            }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            InputAction::MoveLeft => "Move Left",
+            InputAction::MoveRight => "Move Right",
+            InputAction::Jump => "Jump",
+            InputAction::Dash => "Dash",
+            InputAction::Pause => "Pause",
+        }
+    }
+}
+
+/// How far the stick has to be tilted before it counts as a direction
+pub const STICK_DEADZONE: f32 = 0.5;
+
+/// Maps every [`InputAction`] to the key that triggers it
+///
+/// ## Example
+///
+/// ```ron
+/// (
+///     keyboard: {
+///         MoveLeft: KeyA,
+///         MoveRight: KeyD,
+///         Jump: Space,
+///         Dash: ShiftLeft,
+///         Pause: Escape,
+///     },
+/// )
+/// ```
+#[derive(Resource, Serialize, Deserialize, Debug, Clone)]
+pub struct InputBindings {
+    pub keyboard: BTreeMap<InputAction, KeyCode>,
+}
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        Self {
+            keyboard: BTreeMap::from([
+                (InputAction::MoveLeft, KeyCode::KeyA),
+                (InputAction::MoveRight, KeyCode::KeyD),
+                (InputAction::Jump, KeyCode::Space),
+                (InputAction::Dash, KeyCode::ShiftLeft),
+                (InputAction::Pause, KeyCode::Escape),
+            ]),
+        }
+    }
+}
+
+impl SaveData for InputBindings {
+    const KEY: &'static str = "input_bindings";
+    const VERSION: u32 = 1;
+}
+
+impl InputBindings {
+    pub fn key(&self, action: InputAction) -> Option<KeyCode> {
+        self.keyboard.get(&action).copied()
+    }
+
+    /// Binds `key` to `action`, if `key` was already bound to another action
+    /// the two actions swap keys so that no key triggers two actions.
+    pub fn rebind(&mut self, action: InputAction, key: KeyCode) {
+        match self.keyboard.insert(action, key) {
+            Some(previous_key) => {
+                for (other_action, other_key) in self.keyboard.iter_mut() {
+                    if *other_action != action && *other_key == key {
+                        *other_key = previous_key;
+                    }
+                }
+            }
+            None => {
+                self.keyboard
+                    .retain(|other_action, other_key| *other_action == action || *other_key != key);
+            }
+        }
+    }
+}
+
+/// Loads the saved bindings, actions missing from the save (e.g. added in a
+/// later version) fall back to their default key
+fn load_bindings() -> InputBindings {
+    let mut bindings = save::load::<InputBindings>();
+
+    for (action, key) in InputBindings::default().keyboard {
+        if !bindings.keyboard.contains_key(&action)
+            && !bindings.keyboard.values().any(|k| *k == key)
+        {
+            bindings.keyboard.insert(action, key);
+        }
+    }
+
+    bindings
+}
+
+/// The action that is waiting for a key to be bound to it
+#[derive(Resource, Default)]
+pub struct Rebinding(pub Option<InputAction>);
+
+/// Returns a short human readable name for `key`, i.e. `KeyCode::KeyD` => `D`
+pub fn key_name(key: KeyCode) -> String {
+    let name = format!("{:?}", key);
+
+    name.strip_prefix("Key")
+        .or_else(|| name.strip_prefix("Digit"))
+        .unwrap_or(&name)
+        .to_string()
+}
+
+fn update_action_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    touch_actions: Res<TouchActions>,
+    bindings: Res<InputBindings>,
+    rebinding: Res<Rebinding>,
+    mut action_input: ResMut<ButtonInput<InputAction>>,
+) {
+    action_input.clear();
+
+    for action in InputAction::ALL {
+        // While rebinding every key press belongs to the rebinding
+        if rebinding.0.is_some() {
+            action_input.release(action);
+            continue;
+        }
+
+        let key = bindings.key(action);
+        let held = touch_actions.pressed.contains(&action)
+            || gamepads
+                .iter()
+                .any(|gamepad| action.is_gamepad_active(gamepad));
+
+        // Keys only press an action on the frame they are pressed, so that a key
+        // held down while it was being bound doesn't trigger the action
+        if key.is_some_and(|key| keyboard.just_pressed(key)) || held {
+            action_input.press(action);
+        } else if !key.is_some_and(|key| keyboard.pressed(key)) {
+            action_input.release(action);
+        }
+    }
+}
+
+fn rebind_action(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut rebinding: ResMut<Rebinding>,
+    mut bindings: ResMut<InputBindings>,
+) {
+    let Some(action) = rebinding.0 else {
+        return;
+    };
+
+    if let Some(key) = keyboard.get_just_pressed().next() {
+        log::info!("Binding {:?} to {:?}", action, key);
+        bindings.rebind(action, *key);
+        save::store(&*bindings);
+        rebinding.0 = None;
+    }
+}
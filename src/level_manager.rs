@@ -1,7 +1,12 @@
-use crate::{time::RestartTimeEvent, GameState};
+use crate::{
+    save::{self, SaveData},
+    time::RestartTimeEvent,
+    GameState,
+};
 use better_default::Default;
 use bevy::prelude::*;
 use bevy_ecs_ldtk::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
 pub struct LevelManager;
@@ -10,6 +15,8 @@ impl Plugin for LevelManager {
     fn build(&self, app: &mut App) {
         app.insert_resource(CurrentLevelInfo::default())
             .insert_resource(AllLevels::default())
+            .insert_resource(LevelProgress::default())
+            .add_systems(Startup, load_level_progress)
             .add_systems(
                 Update,
                 sync_level_changes.run_if(in_state(GameState::PlayingScreen)),
@@ -54,6 +61,38 @@ pub struct AllLevels {
     pub all_levels: BTreeMap<i32, String>,
 }
 
+/// Tracks which levels the player is allowed to choose from the levels menu
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, Default)]
+#[default(highest_unlocked_level: 0)]
+pub struct LevelProgress {
+    pub highest_unlocked_level: i32,
+}
+
+impl LevelProgress {
+    pub fn is_unlocked(&self, level_id: i32) -> bool {
+        level_id <= self.highest_unlocked_level
+    }
+
+    /// Unlocks every level up to `level_id`, returns `true` if anything new was unlocked
+    pub fn unlock(&mut self, level_id: i32) -> bool {
+        if self.is_unlocked(level_id) {
+            return false;
+        }
+
+        self.highest_unlocked_level = level_id;
+        true
+    }
+}
+
+impl SaveData for LevelProgress {
+    const KEY: &'static str = "level_progress";
+    const VERSION: u32 = 1;
+}
+
+fn load_level_progress(mut level_progress: ResMut<LevelProgress>) {
+    *level_progress = save::load::<LevelProgress>();
+}
+
 fn sync_level_changes(
     mut commands: Commands,
     current_level_info: Res<CurrentLevelInfo>,
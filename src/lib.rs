@@ -1,32 +1,56 @@
 use assets::AssetsManagerPlugin;
 use bevy::prelude::*;
 use bevy::utils::{Duration, Instant};
-#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
-use bevy::window::WindowMode;
 use bevy_ecs_ldtk::LdtkWorldBundle;
 use bevy_light_2d::plugin::Light2dPlugin;
+use chase::ChasePlugin;
+use checkpoint::CheckpointPlugin;
+use collectible::CollectiblePlugin;
 use hostile_entity::HostilePlugin;
+use hostile_spawner::HostileSpawnerPlugin;
+use input::{ActionInputPlugin, InputAction};
 use level_manager::LevelManager;
+use moving_platform::MovingPlatformPlugin;
+use pickups::PickupsPlugin;
 use player::PlayerPlugin;
-use screens::ScreensPlugin;
+use projectile::ProjectilePlugin;
+use screens::{settings_screen::SettingsReturnState, ScreensPlugin};
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+use settings::Settings;
+use settings::{BackgroundMusic, SettingsPlugin};
 use special_tiles::SpecialTilesPlugin;
 use sprite_animation::SpriteAnimationPlugin;
+use stomp::StompPlugin;
 use time::TimeTakenPlugin;
+use touch_controls::TouchControlsPlugin;
 use tutorial::GameTutorialPlugin;
 use walls::WallPlugin;
 
 pub mod assets;
 pub mod camera;
+pub mod chase;
+pub mod checkpoint;
+pub mod collectible;
 pub mod colliders;
+pub mod config;
 pub mod ground_detection;
 pub mod hostile_entity;
+pub mod hostile_spawner;
+pub mod input;
 pub mod level_manager;
+pub mod moving_platform;
 pub mod physics;
+pub mod pickups;
 pub mod player;
+pub mod projectile;
+pub mod save;
 pub mod screens;
+pub mod settings;
 pub mod special_tiles;
 pub mod sprite_animation;
+pub mod stomp;
 pub mod time;
+pub mod touch_controls;
 pub mod tutorial;
 pub mod utils;
 pub mod walls;
@@ -44,6 +68,14 @@ impl Plugin for EntitySpawnerPlugin {
             .add_plugins(Light2dPlugin)
             .add_plugins(PlayerPlugin)
             .add_plugins(HostilePlugin)
+            .add_plugins(HostileSpawnerPlugin)
+            .add_plugins(ChasePlugin)
+            .add_plugins(ProjectilePlugin)
+            .add_plugins(StompPlugin)
+            .add_plugins(CheckpointPlugin)
+            .add_plugins(CollectiblePlugin)
+            .add_plugins(PickupsPlugin)
+            .add_plugins(MovingPlatformPlugin)
             .add_plugins(SpriteAnimationPlugin);
     }
 }
@@ -61,8 +93,10 @@ pub struct BasePlugin;
 impl Plugin for BasePlugin {
     fn build(&self, app: &mut App) {
         app.insert_state(GameState::default())
-            .insert_resource(Time::<Fixed>::from_seconds(1.))
             .add_plugins(AssetsManagerPlugin)
+            .add_plugins(SettingsPlugin)
+            .add_plugins(ActionInputPlugin)
+            .add_plugins(TouchControlsPlugin)
             .add_plugins(ScreensPlugin)
             .add_plugins(LevelManager)
             .add_plugins(GameTutorialPlugin)
@@ -98,14 +132,16 @@ pub enum GameState {
     LevelsMenuScreen,
     PlayingScreen,
     CreditScreen,
+    SettingsScreen,
 }
 
 fn base_game_system(
-    keyboard: Res<ButtonInput<KeyCode>>,
+    input: Res<ButtonInput<InputAction>>,
     game_state: Res<State<GameState>>,
     mut next_game_state: ResMut<NextState<GameState>>,
+    settings_return_state: Res<SettingsReturnState>,
 ) {
-    if keyboard.just_pressed(KeyCode::Escape) {
+    if input.just_pressed(InputAction::Pause) {
         match *game_state.get() {
             GameState::PauseScreen => {
                 next_game_state.set(GameState::PlayingScreen);
@@ -113,6 +149,9 @@ fn base_game_system(
             GameState::PlayingScreen => {
                 next_game_state.set(GameState::PauseScreen);
             }
+            GameState::SettingsScreen => {
+                next_game_state.set(settings_return_state.0.clone());
+            }
             _ => {}
         }
     }
@@ -160,21 +199,13 @@ fn play_game_background_music(mut commands: Commands, audio_assets: Res<assets::
     commands.spawn((
         AudioPlayer(audio_assets.smooth_lovin.clone()),
         PlaybackSettings::LOOP,
+        BackgroundMusic,
     ));
 }
 
 #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
-fn full_screen(keyboard: Res<ButtonInput<KeyCode>>, mut windows: Query<&mut Window>) {
+fn full_screen(keyboard: Res<ButtonInput<KeyCode>>, mut settings: ResMut<Settings>) {
     if keyboard.just_pressed(KeyCode::F11) {
-        for mut window in &mut windows {
-            match window.mode {
-                WindowMode::Fullscreen(_) => {
-                    window.mode = WindowMode::Windowed;
-                }
-                _ => {
-                    window.mode = WindowMode::Fullscreen(MonitorSelection::Current);
-                }
-            }
-        }
+        settings.fullscreen = !settings.fullscreen;
     }
 }
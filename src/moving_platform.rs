@@ -0,0 +1,199 @@
+use crate::{
+    colliders::ColliderBundle, ground_detection::GroundSensor, hostile_entity::patrol_points,
+    GameState,
+};
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+use bevy_rapier2d::prelude::Velocity;
+
+pub struct MovingPlatformPlugin;
+
+impl Plugin for MovingPlatformPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_ldtk_entity::<MovingPlatform>("Moving_Platform")
+            .add_systems(
+                Update,
+                (move_platforms, carry_riders)
+                    .chain()
+                    .run_if(in_state(GameState::PlayingScreen)),
+            );
+    }
+}
+
+#[derive(Default, Bundle, LdtkEntity)]
+pub struct MovingPlatform {
+    #[sprite_sheet]
+    pub sprite: Sprite,
+    #[from_entity_instance]
+    pub collider_bundle: ColliderBundle,
+    #[ldtk_entity]
+    pub path: PlatformPath,
+    pub moving_platform_entity: MovingPlatformEntity,
+}
+
+#[derive(Default, Component)]
+pub struct MovingPlatformEntity;
+
+/// How a path continues after its last point, set by the LDtk `Path_Mode` enum
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PathMode {
+    /// Goes from the last point straight back to the first one
+    Loop,
+    /// Turns around at the first and last point
+    #[default]
+    PingPong,
+    /// Stops at the last point
+    OneShot,
+}
+
+impl From<&str> for PathMode {
+    fn from(value: &str) -> Self {
+        match value {
+            "Loop" => PathMode::Loop,
+            "Ping_Pong" => PathMode::PingPong,
+            "One_Shot" => PathMode::OneShot,
+            _ => panic!("Unsupported Path_Mode {}", value),
+        }
+    }
+}
+
+impl PathMode {
+    /// Point to move towards after reaching `index` out of `len` points, `None` once a
+    /// [`PathMode::OneShot`] path is done. `forward` is updated when turning around.
+    pub fn next_index(self, index: usize, forward: &mut bool, len: usize) -> Option<usize> {
+        match self {
+            PathMode::Loop => Some((index + 1) % len),
+            PathMode::PingPong => {
+                if index == len - 1 {
+                    *forward = false;
+                } else if index == 0 {
+                    *forward = true;
+                }
+
+                Some(if *forward { index + 1 } else { index - 1 })
+            }
+            PathMode::OneShot => (index + 1 < len).then_some(index + 1),
+        }
+    }
+}
+
+/// Route of a moving platform, the points are parsed like [`crate::hostile_entity::Patrol`]
+#[derive(Component, Debug, better_default::Default)]
+#[default(index: 1, forward: true, speed: 60., wait_time: 1.)]
+pub struct PlatformPath {
+    pub points: Vec<Vec2>,
+    /// The point that is being moved towards
+    pub index: usize,
+    pub forward: bool,
+    pub mode: PathMode,
+    /// Pixels per second
+    pub speed: f32,
+    /// Seconds to stand still at the first and last point
+    pub wait_time: f32,
+    pub wait_time_left: f32,
+    /// Set once a [`PathMode::OneShot`] path reached its last point
+    pub finished: bool,
+}
+
+impl PlatformPath {
+    fn is_at_endpoint(&self) -> bool {
+        self.index == 0 || self.index == self.points.len() - 1
+    }
+
+    fn advance(&mut self) {
+        match self
+            .mode
+            .next_index(self.index, &mut self.forward, self.points.len())
+        {
+            Some(index) => self.index = index,
+            None => self.finished = true,
+        }
+    }
+}
+
+impl LdtkEntity for PlatformPath {
+    fn bundle_entity(
+        entity_instance: &EntityInstance,
+        layer_instance: &LayerInstance,
+        _tileset: Option<&Handle<Image>>,
+        _tileset_definition: Option<&TilesetDefinition>,
+        _asset_server: &AssetServer,
+        _texture_atlases: &mut Assets<TextureAtlasLayout>,
+    ) -> Self {
+        let speed = *entity_instance
+            .get_float_field("speed")
+            .expect("speed field should be correctly typed");
+        let wait_time = *entity_instance
+            .get_float_field("wait_time")
+            .expect("wait_time field should be correctly typed");
+        let mode = entity_instance
+            .get_enum_field("mode")
+            .expect("mode field should be correctly typed");
+
+        Self {
+            points: patrol_points(entity_instance, &LayerMetadata::from(layer_instance)),
+            mode: PathMode::from(mode.as_str()),
+            speed,
+            wait_time,
+            ..default()
+        }
+    }
+}
+
+fn move_platforms(
+    mut query: Query<(&Transform, &mut Velocity, &mut PlatformPath)>,
+    time: Res<Time>,
+) {
+    let delta = time.delta_secs();
+
+    for (transform, mut velocity, mut path) in &mut query {
+        if path.points.len() <= 1 || path.finished || delta == 0. {
+            velocity.linvel = Vec2::ZERO;
+            continue;
+        }
+
+        if path.wait_time_left > 0. {
+            path.wait_time_left -= delta;
+            velocity.linvel = Vec2::ZERO;
+            continue;
+        }
+
+        let to_target = path.points[path.index] - transform.translation.truncate();
+
+        if to_target.length() <= path.speed * delta {
+            // Land exactly on the point instead of overshooting it
+            velocity.linvel = to_target / delta;
+
+            if path.is_at_endpoint() {
+                path.wait_time_left = path.wait_time;
+            }
+            path.advance();
+        } else {
+            velocity.linvel = to_target.normalize() * path.speed;
+        }
+    }
+}
+
+/// Moves everything standing on a moving platform sideways along with it, the player has
+/// no friction so it would slide off otherwise. The contact already moves riders up and
+/// down.
+fn carry_riders(
+    platform_query: Query<&Velocity, With<MovingPlatformEntity>>,
+    ground_sensor_query: Query<&GroundSensor>,
+    mut rider_query: Query<&mut Transform, Without<MovingPlatformEntity>>,
+    time: Res<Time>,
+) {
+    for ground_sensor in &ground_sensor_query {
+        let Some(platform_velocity) = ground_sensor
+            .intersecting_ground_entities
+            .iter()
+            .find_map(|entity| platform_query.get(*entity).ok())
+        else {
+            continue;
+        };
+
+        if let Ok(mut transform) = rider_query.get_mut(ground_sensor.ground_detection_entity) {
+            transform.translation.x += platform_velocity.linvel.x * time.delta_secs();
+        }
+    }
+}
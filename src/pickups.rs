@@ -0,0 +1,133 @@
+use crate::{
+    assets::AudioAssets,
+    player::{HealthBar, PlayerEntity},
+    GameState, GRID_SIZE,
+};
+use bevy::prelude::*;
+use bevy_ecs_ldtk::prelude::*;
+use bevy_light_2d::prelude::PointLight2d;
+use bevy_rapier2d::prelude::*;
+
+pub struct PickupsPlugin;
+
+impl Plugin for PickupsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_ldtk_entity::<HeartPickup>("Heart")
+            .register_ldtk_entity::<HeartContainer>("Heart_Container")
+            .add_systems(
+                Update,
+                (style_heart_containers, pick_up_hearts).run_if(in_state(GameState::PlayingScreen)),
+            );
+    }
+}
+
+/// Restores health, it is left in place while the player's health is full
+#[derive(better_default::Default, Bundle, LdtkEntity)]
+#[default(
+    collider: Collider::ball(GRID_SIZE as f32 / 3.),
+    active_events: ActiveEvents::COLLISION_EVENTS
+)]
+pub struct HeartPickup {
+    #[sprite("icons/icon_heart.png")]
+    pub sprite: Sprite,
+    pub heart: HeartEntity,
+    pub collider: Collider,
+    pub sensor: Sensor,
+    pub active_events: ActiveEvents,
+}
+
+#[derive(better_default::Default, Component)]
+#[default(heal: 1)]
+pub struct HeartEntity {
+    pub heal: u8,
+}
+
+/// Rare pickup that adds heart slots to the health bar until the level is left
+#[derive(better_default::Default, Bundle, LdtkEntity)]
+#[default(
+    collider: Collider::ball(GRID_SIZE as f32 / 2.),
+    active_events: ActiveEvents::COLLISION_EVENTS,
+    point_light: PointLight2d {
+        intensity: 0.8,
+        radius: GRID_SIZE as f32 * 3.,
+        ..default()
+    }
+)]
+pub struct HeartContainer {
+    #[sprite("icons/icon_heart.png")]
+    pub sprite: Sprite,
+    pub heart_container: HeartContainerEntity,
+    pub collider: Collider,
+    pub sensor: Sensor,
+    pub active_events: ActiveEvents,
+    pub point_light: PointLight2d,
+}
+
+#[derive(better_default::Default, Component)]
+#[default(extra_hearts: 1)]
+pub struct HeartContainerEntity {
+    pub extra_hearts: u8,
+}
+
+/// Heart containers share the heart icon, they are drawn bigger and golden to stand out
+fn style_heart_containers(mut query: Query<&mut Sprite, Added<HeartContainerEntity>>) {
+    for mut sprite in &mut query {
+        sprite.custom_size = Some(Vec2::splat(GRID_SIZE as f32 * 1.5));
+        // hsl(45, 100%, 60%)
+        sprite.color = Color::hsl(45., 1., 0.6);
+    }
+}
+
+fn pick_up_hearts(
+    mut collision_events: EventReader<CollisionEvent>,
+    mut player_query: Query<(Entity, &mut HealthBar), With<PlayerEntity>>,
+    heart_query: Query<(Entity, &HeartEntity)>,
+    heart_container_query: Query<(Entity, &HeartContainerEntity)>,
+    audio_assets: Res<AudioAssets>,
+    mut commands: Commands,
+) {
+    let Ok((player_entity, mut health_bar)) = player_query.get_single_mut() else {
+        return;
+    };
+
+    for collision in collision_events.read() {
+        let CollisionEvent::Started(entity_one, entity_two, ..) = *collision else {
+            continue;
+        };
+
+        let other_entity = if entity_one == player_entity {
+            entity_two
+        } else if entity_two == player_entity {
+            entity_one
+        } else {
+            continue;
+        };
+
+        if let Ok((heart_entity, heart)) = heart_query.get(other_entity) {
+            if !health_bar.heal(heart.heal) {
+                continue;
+            }
+
+            log::info!("Picked up Heart, health is now {}", health_bar.health);
+            commands.entity(heart_entity).despawn_recursive();
+            commands.spawn((
+                AudioPlayer(audio_assets.button.clone()),
+                PlaybackSettings::REMOVE,
+            ));
+        } else if let Ok((heart_container_entity, heart_container)) =
+            heart_container_query.get(other_entity)
+        {
+            health_bar.raise_max(heart_container.extra_hearts);
+
+            log::info!(
+                "Picked up Heart Container, max health is now {}",
+                health_bar.max
+            );
+            commands.entity(heart_container_entity).despawn_recursive();
+            commands.spawn((
+                AudioPlayer(audio_assets.jump_boost.clone()),
+                PlaybackSettings::REMOVE,
+            ));
+        }
+    }
+}
@@ -1,25 +1,31 @@
 use crate::{
-    assets::{AudioAssets, EntitySpriteAssets, IconsAssets},
+    assets::{AudioAssets, EntitySpriteAssets, FontAssets, IconsAssets},
     camera::MainCamera,
     colliders::ColliderBundle,
-    ground_detection::{GroundDetection, GroundDetectionPlugin},
+    config::RonConfigPlugin,
+    ground_detection::{GroundDetection, GroundDetectionPlugin, WallDetection, WallSide},
+    input::InputAction,
     level_manager::CurrentLevelInfo,
     screens::despawn_screen,
+    settings::BackgroundMusic,
     sprite_animation::Animation,
-    time::RecordTimeEvent,
     GameState, GRID_SIZE,
 };
-use bevy::utils::Duration;
+use bevy::utils::{Duration, HashSet};
 use bevy::{color::palettes::css::YELLOW, prelude::*, time::common_conditions::on_real_timer};
 use bevy_ecs_ldtk::prelude::*;
 use bevy_light_2d::prelude::{AmbientLight2d, PointLight2d};
-use bevy_rapier2d::prelude::Velocity;
+use bevy_rapier2d::prelude::{GravityScale, Velocity};
+use serde::Deserialize;
 
 pub struct PlayerPlugin;
 
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
         app.register_ldtk_entity::<Player>("Player")
+            .add_plugins(RonConfigPlugin::<PlayerMovementConfig>::new(
+                "config/player_movement.ron",
+            ))
             .add_plugins(GroundDetectionPlugin)
             .add_systems(OnEnter(GameState::PlayingScreen), spawn_healthbar)
             .add_systems(
@@ -29,8 +35,11 @@ impl Plugin for PlayerPlugin {
             .add_systems(
                 Update,
                 (
+                    player_dash.before(player_movement),
                     player_movement,
                     sync_healthbar,
+                    sync_dash_indicator,
+                    update_invulnerability,
                     handle_player_animation,
                     update_blindness,
                 )
@@ -43,10 +52,6 @@ impl Plugin for PlayerPlugin {
                         .and(on_real_timer(Duration::from_secs_f32(0.5))),
                 ),
             )
-            .add_systems(
-                FixedUpdate,
-                continue_taking_damage.run_if(in_state(GameState::PlayingScreen)),
-            )
             .add_systems(
                 OnTransition {
                     exited: GameState::PlayingScreen,
@@ -78,8 +83,12 @@ pub struct Player {
     #[from_entity_instance]
     pub collider_bundle: ColliderBundle,
     pub ground_detection: GroundDetection,
+    pub wall_detection: WallDetection,
     pub health_bar: HealthBar,
     pub player_state: PlayerState,
+    pub jump_state: JumpState,
+    pub dash_state: DashState,
+    pub hostile_contacts: HostileContacts,
     pub animation: Animation,
 }
 
@@ -94,42 +103,356 @@ pub enum PlayerState {
 }
 
 #[derive(better_default::Default, Component)]
-#[default(health: 6)]
+#[default(health: 6, max: 6)]
 pub struct HealthBar {
     pub health: u8,
+    /// Number of heart slots, raised by heart containers
+    pub max: u8,
+}
+
+impl HealthBar {
+    /// Restores up to `amount` health without exceeding `max`, returns `false` when
+    /// the health was already full
+    pub fn heal(&mut self, amount: u8) -> bool {
+        if self.health >= self.max {
+            return false;
+        }
+
+        self.health = self.health.saturating_add(amount).min(self.max);
+        true
+    }
+
+    /// Adds `amount` heart slots and fills up the health
+    pub fn raise_max(&mut self, amount: u8) {
+        self.max = self.max.saturating_add(amount);
+        self.health = self.max;
+    }
+}
+
+/// Tunes how the player moves, loaded from `assets/config/player_movement.ron`.
+///
+/// Speeds are in pixels per second, accelerations in pixels per second squared
+/// and durations in seconds. Every field has to be in the RON file, the defaults are
+/// only used until it is loaded.
+#[derive(Asset, TypePath, Resource, Deserialize, Debug, Clone, better_default::Default)]
+#[default(
+    max_speed: 200.,
+    ground_acceleration: 2000.,
+    ground_deceleration: 2500.,
+    air_acceleration: 1200.,
+    air_deceleration: 600.,
+    jump_velocity: 400.,
+    gravity_scale: 1.,
+    coyote_time: 0.1,
+    jump_buffer_time: 0.12,
+    jump_cut_multiplier: 0.5,
+    wall_slide_speed: 60.,
+    wall_jump_horizontal_velocity: 250.,
+    wall_jump_vertical_velocity: 380.,
+    wall_jump_control_lock: 0.15,
+    dash_speed: 500.,
+    dash_duration: 0.15,
+    dash_cooldown: 0.8,
+    knockback_horizontal_velocity: 220.,
+    knockback_vertical_velocity: 250.,
+    knockback_control_lock: 0.25,
+    invulnerability_time: 1.,
+    ice_acceleration: 400.,
+    ice_deceleration: 150.,
+    stomp_bounce_velocity: 320.,
+    launch_control_lock: 0.3
+)]
+pub struct PlayerMovementConfig {
+    pub max_speed: f32,
+    /// Used while running on ground, also when turning around
+    pub ground_acceleration: f32,
+    /// Used to come to a stop on ground after letting go of the movement keys
+    pub ground_deceleration: f32,
+    pub air_acceleration: f32,
+    pub air_deceleration: f32,
+    pub jump_velocity: f32,
+    pub gravity_scale: f32,
+    /// How long after leaving a ledge the player can still jump
+    pub coyote_time: f32,
+    /// How long a jump pressed mid-air is remembered, so that it fires on landing
+    pub jump_buffer_time: f32,
+    /// Vertical velocity is multiplied by this when jump is released while rising
+    pub jump_cut_multiplier: f32,
+    /// Maximum falling speed while holding towards a wall
+    pub wall_slide_speed: f32,
+    /// Speed away from the wall when jumping off of it
+    pub wall_jump_horizontal_velocity: f32,
+    pub wall_jump_vertical_velocity: f32,
+    /// How long horizontal input is ignored after a wall jump, so that holding
+    /// towards the wall doesn't immediately pull the player back
+    pub wall_jump_control_lock: f32,
+    /// Horizontal speed while dashing, gravity is ignored during the dash
+    pub dash_speed: f32,
+    pub dash_duration: f32,
+    /// Counted from the start of the dash
+    pub dash_cooldown: f32,
+    /// Speed away from the hostile entity after taking damage
+    pub knockback_horizontal_velocity: f32,
+    pub knockback_vertical_velocity: f32,
+    pub knockback_control_lock: f32,
+    /// How long the player can't take damage again after being hit
+    pub invulnerability_time: f32,
+    /// Replaces the ground acceleration while standing on ice
+    pub ice_acceleration: f32,
+    pub ice_deceleration: f32,
+    /// Vertical speed after landing on a hostile entity
+    pub stomp_bounce_velocity: f32,
+    /// How long horizontal input is ignored after an angled jump booster
+    pub launch_control_lock: f32,
+}
+
+impl PlayerMovementConfig {
+    fn acceleration(&self, ground_detection: &GroundDetection, is_moving: bool) -> f32 {
+        match (
+            ground_detection.on_ground,
+            ground_detection.on_slippery_ground,
+            is_moving,
+        ) {
+            (true, true, true) => self.ice_acceleration,
+            (true, true, false) => self.ice_deceleration,
+            (true, false, true) => self.ground_acceleration,
+            (true, false, false) => self.ground_deceleration,
+            (false, _, true) => self.air_acceleration,
+            (false, _, false) => self.air_deceleration,
+        }
+    }
+}
+
+#[derive(Default, Component)]
+pub struct JumpState {
+    /// Seconds since the player was last on ground, `None` once the coyote time is used up
+    pub time_since_grounded: Option<f32>,
+    /// Seconds since jump was pressed, `None` if there is no buffered jump
+    pub time_since_jump_pressed: Option<f32>,
+    /// `true` from the jump until the player starts falling
+    pub is_jumping: bool,
+    /// Seconds left until horizontal input is applied again, i.e. after a wall jump
+    /// or being knocked back
+    pub control_lock: f32,
+}
+
+#[derive(Default, Component)]
+pub struct DashState {
+    /// Seconds left of the current dash
+    pub time_left: f32,
+    /// Seconds until the player can dash again
+    pub cooldown: f32,
+    /// `1.` while dashing right and `-1.` while dashing left
+    pub direction: f32,
+    /// Only one dash is allowed in the air until the player lands again
+    pub used_air_dash: bool,
+}
+
+impl DashState {
+    pub fn is_dashing(&self) -> bool {
+        self.time_left > 0.
+    }
 }
 
 /// Represents that an Entity is blind
 #[derive(Default, Component)]
 pub struct Blinded(pub Timer);
 
-// TODO: Add auto-snip to the diagonal tiles
+#[allow(clippy::type_complexity)]
+fn player_dash(
+    input: Res<ButtonInput<InputAction>>,
+    movement_config: Res<PlayerMovementConfig>,
+    time: Res<Time>,
+    mut query: Query<
+        (
+            &mut Velocity,
+            &mut GravityScale,
+            &Sprite,
+            &GroundDetection,
+            &mut DashState,
+        ),
+        With<PlayerEntity>,
+    >,
+) {
+    for (mut velocity, mut gravity_scale, player_sprite, ground_detection, mut dash_state) in
+        &mut query
+    {
+        dash_state.cooldown = (dash_state.cooldown - time.delta_secs()).max(0.);
+
+        if ground_detection.on_ground {
+            dash_state.used_air_dash = false;
+        }
+
+        if dash_state.is_dashing() {
+            dash_state.time_left -= time.delta_secs();
+
+            velocity.linvel = if dash_state.is_dashing() {
+                Vec2::new(dash_state.direction * movement_config.dash_speed, 0.)
+            } else {
+                // Continue at running speed instead of stopping abruptly
+                Vec2::new(dash_state.direction * movement_config.max_speed, 0.)
+            };
+            continue;
+        }
+
+        if input.just_pressed(InputAction::Dash)
+            && dash_state.cooldown <= 0.
+            && !dash_state.used_air_dash
+        {
+            dash_state.direction = if player_sprite.flip_x { -1. } else { 1. };
+            dash_state.time_left = movement_config.dash_duration;
+            dash_state.cooldown = movement_config.dash_cooldown;
+            dash_state.used_air_dash = !ground_detection.on_ground;
+
+            velocity.linvel = Vec2::new(dash_state.direction * movement_config.dash_speed, 0.);
+            gravity_scale.set_if_neq(GravityScale(0.));
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
 fn player_movement(
-    input: Res<ButtonInput<KeyCode>>,
-    mut query: Query<(&mut Velocity, &mut Sprite, &GroundDetection), With<PlayerEntity>>,
+    input: Res<ButtonInput<InputAction>>,
+    movement_config: Res<PlayerMovementConfig>,
+    time: Res<Time>,
+    mut query: Query<
+        (
+            &mut Velocity,
+            &mut GravityScale,
+            &mut Sprite,
+            &GroundDetection,
+            &WallDetection,
+            &mut JumpState,
+            &DashState,
+        ),
+        With<PlayerEntity>,
+    >,
 ) {
-    for (mut velocity, mut player_sprite, ground_detection) in &mut query {
-        let right = if input.pressed(KeyCode::KeyD) {
+    for (
+        mut velocity,
+        mut gravity_scale,
+        mut player_sprite,
+        ground_detection,
+        wall_detection,
+        mut jump_state,
+        dash_state,
+    ) in &mut query
+    {
+        // The dash has full control over the velocity
+        if dash_state.is_dashing() {
+            continue;
+        }
+
+        let right = if input.pressed(InputAction::MoveRight) {
             player_sprite.flip_x = false;
             1.
         } else {
             0.
         };
-        let left = if input.pressed(KeyCode::KeyA) {
+        let left = if input.pressed(InputAction::MoveLeft) {
             player_sprite.flip_x = true;
             1.
         } else {
             0.
         };
 
-        velocity.linvel.x = (right - left) * 200.;
+        if jump_state.is_jumping && velocity.linvel.y <= 0. {
+            jump_state.is_jumping = false;
+        }
 
-        if input.just_pressed(KeyCode::Space) && ground_detection.on_ground {
-            velocity.linvel.y = 400.;
+        let target_speed = (right - left) * movement_config.max_speed;
+        let max_speed_change =
+            movement_config.acceleration(ground_detection, target_speed != 0.) * time.delta_secs();
+
+        match ground_detection.slope_normal {
+            Some(normal) if ground_detection.on_ground && !jump_state.is_jumping => {
+                // Run along the slope instead of into or off of it, and without gravity
+                // so that standing still doesn't slide the player down
+                let tangent = Vec2::new(normal.y, -normal.x);
+                let speed =
+                    move_towards(velocity.linvel.dot(tangent), target_speed, max_speed_change);
+                velocity.linvel = tangent * speed;
+                gravity_scale.set_if_neq(GravityScale(0.));
+            }
+            _ => {
+                if jump_state.control_lock > 0. {
+                    jump_state.control_lock -= time.delta_secs();
+                } else {
+                    velocity.linvel.x =
+                        move_towards(velocity.linvel.x, target_speed, max_speed_change);
+                }
+                gravity_scale.set_if_neq(GravityScale(movement_config.gravity_scale));
+            }
+        }
+
+        let wall_side = wall_detection
+            .wall_side()
+            .filter(|_| !ground_detection.on_ground);
+
+        let is_holding_towards_wall = match wall_side {
+            Some(WallSide::Left) => left > 0.,
+            Some(WallSide::Right) => right > 0.,
+            None => false,
+        };
+
+        if is_holding_towards_wall && velocity.linvel.y < 0. {
+            velocity.linvel.y = velocity.linvel.y.max(-movement_config.wall_slide_speed);
+        }
+
+        // The ground sensor still touches the ground for a few frames after jumping
+        if ground_detection.on_ground && !jump_state.is_jumping {
+            jump_state.time_since_grounded = Some(0.);
+        } else {
+            jump_state.time_since_grounded = jump_state
+                .time_since_grounded
+                .map(|t| t + time.delta_secs())
+                .filter(|t| *t <= movement_config.coyote_time);
+        }
+
+        if input.just_pressed(InputAction::Jump) {
+            jump_state.time_since_jump_pressed = Some(0.);
+        } else {
+            jump_state.time_since_jump_pressed = jump_state
+                .time_since_jump_pressed
+                .map(|t| t + time.delta_secs())
+                .filter(|t| *t <= movement_config.jump_buffer_time);
+        }
+
+        if jump_state.time_since_jump_pressed.is_some() && jump_state.time_since_grounded.is_some()
+        {
+            velocity.linvel.y = movement_config.jump_velocity;
+            jump_state.time_since_jump_pressed = None;
+            jump_state.time_since_grounded = None;
+            jump_state.is_jumping = true;
+        } else if let (Some(wall_side), Some(_)) = (wall_side, jump_state.time_since_jump_pressed) {
+            // Kick off the wall
+            let direction = match wall_side {
+                WallSide::Left => 1.,
+                WallSide::Right => -1.,
+            };
+            velocity.linvel = Vec2::new(
+                direction * movement_config.wall_jump_horizontal_velocity,
+                movement_config.wall_jump_vertical_velocity,
+            );
+            player_sprite.flip_x = direction < 0.;
+            jump_state.time_since_jump_pressed = None;
+            jump_state.is_jumping = true;
+            jump_state.control_lock = movement_config.wall_jump_control_lock;
+        }
+
+        // Releasing jump early cuts the jump short
+        if jump_state.is_jumping && !input.pressed(InputAction::Jump) {
+            velocity.linvel.y *= movement_config.jump_cut_multiplier;
+            jump_state.is_jumping = false;
         }
     }
 }
 
+/// Moves `current` towards `target` without overshooting it
+pub fn move_towards(current: f32, target: f32, max_delta: f32) -> f32 {
+    current + (target - current).clamp(-max_delta, max_delta)
+}
+
 #[allow(clippy::type_complexity)]
 fn handle_player_animation(
     mut query: Query<
@@ -158,14 +481,29 @@ fn handle_player_animation(
 #[derive(Component)]
 pub struct HealthBarContext;
 
+/// Holds the hearts of the health bar, the rest of [`HealthBarContext`] is left untouched
+/// when the hearts are updated
+#[derive(Component)]
+pub struct HeartsContainer;
+
+/// Covers the dash icon from the bottom, shrinking as the dash cooldown runs out
+#[derive(Component)]
+pub struct DashCooldownOverlay;
+
+/// Hostile entities the player is currently touching
+#[derive(Default, Component)]
+pub struct HostileContacts(pub HashSet<Entity>);
+
+/// The player can't take damage until the timer finishes, the sprite flashes meanwhile
 #[derive(Component)]
 #[require(HealthBar)]
-pub struct ContinueTakingDamage(pub u8);
+pub struct Invulnerable(pub Timer);
 
 fn spawn_healthbar(
     mut commands: Commands,
     health_bar_query: Query<&HealthBar, With<PlayerEntity>>,
     icons_assets: Res<IconsAssets>,
+    font_assets: Res<FontAssets>,
 ) {
     commands
         .spawn((
@@ -182,93 +520,156 @@ fn spawn_healthbar(
             HealthBarContext,
         ))
         .with_children(|parent| {
-            for health_bar in &health_bar_query {
-                for _ in 0..health_bar.health {
-                    parent
-                        .spawn(Node {
-                            width: Val::Px(30.),
-                            height: Val::Px(30.),
-                            ..default()
-                        })
-                        .with_child(ImageNode {
-                            image: icons_assets.heart_icon.clone(),
-                            ..default()
-                        });
-                }
-            }
+            spawn_dash_indicator(parent, &font_assets);
+
+            parent
+                .spawn((
+                    Node {
+                        display: Display::Flex,
+                        align_items: AlignItems::Center,
+                        column_gap: Val::Px(10.),
+                        ..default()
+                    },
+                    HeartsContainer,
+                ))
+                .with_children(|parent| {
+                    for health_bar in &health_bar_query {
+                        spawn_hearts(parent, health_bar, &icons_assets);
+                    }
+                });
         });
 }
 
-#[allow(clippy::too_many_arguments)]
+fn spawn_dash_indicator(parent: &mut ChildBuilder, font_assets: &FontAssets) {
+    parent
+        .spawn((
+            Node {
+                width: Val::Px(30.),
+                height: Val::Px(30.),
+                border: UiRect::all(Val::Px(2.)),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                overflow: Overflow::clip(),
+                ..default()
+            },
+            BorderColor(Color::WHITE),
+            BorderRadius::all(Val::Px(6.)),
+            // hsl(327, 24%, 16%)
+            BackgroundColor(Color::hsl(327., 0.24, 0.16)),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(">>"),
+                TextColor(Color::WHITE),
+                TextFont {
+                    font: font_assets.default_font.clone(),
+                    font_size: 16.,
+                    ..default()
+                },
+            ));
+            parent.spawn((
+                Node {
+                    width: Val::Percent(100.),
+                    height: Val::Percent(0.),
+                    position_type: PositionType::Absolute,
+                    bottom: Val::Px(0.),
+                    left: Val::Px(0.),
+                    ..default()
+                },
+                // Black With 60% Opacity
+                BackgroundColor(Color::hsla(0., 0., 0., 0.6)),
+                DashCooldownOverlay,
+            ));
+        });
+}
+
+fn sync_dash_indicator(
+    dash_state_query: Query<&DashState, (With<PlayerEntity>, Changed<DashState>)>,
+    mut overlay_query: Query<&mut Node, With<DashCooldownOverlay>>,
+    movement_config: Res<PlayerMovementConfig>,
+) {
+    for dash_state in &dash_state_query {
+        let remaining = if movement_config.dash_cooldown > 0. {
+            dash_state.cooldown / movement_config.dash_cooldown
+        } else {
+            0.
+        };
+
+        for mut node in &mut overlay_query {
+            node.height = Val::Percent(remaining.clamp(0., 1.) * 100.);
+        }
+    }
+}
+
 fn sync_healthbar(
     health_bar_query: Query<&HealthBar, (With<PlayerEntity>, Changed<HealthBar>)>,
-    health_bar_context_query: Query<Entity, With<HealthBarContext>>,
+    hearts_container_query: Query<Entity, With<HeartsContainer>>,
     icons_assets: Res<IconsAssets>,
     mut commands: Commands,
-    current_level_info: Res<CurrentLevelInfo>,
-    mut record_time_event: EventWriter<RecordTimeEvent>,
     mut next_game_state: ResMut<NextState<GameState>>,
     mut time: ResMut<Time<Virtual>>,
 ) {
     for health_bar in &health_bar_query {
         if health_bar.health == 0 {
-            // Game Over
-            record_time_event.send(RecordTimeEvent(current_level_info.current_level_id));
+            // Game Over, the time only counts when the level is finished
             next_game_state.set(GameState::GameOverScreen);
             time.pause();
             return;
         }
 
-        for health_bar_context in &health_bar_context_query {
-            let mut health_bar_context_commands = commands.entity(health_bar_context);
+        for hearts_container in &hearts_container_query {
+            let mut hearts_container_commands = commands.entity(hearts_container);
 
             // Remove the old hearts
-            health_bar_context_commands.despawn_descendants();
+            hearts_container_commands.despawn_descendants();
 
             // Generate Sprite Bundle with all the hearts
-            health_bar_context_commands.with_children(|parent| {
-                // Spawn Health Icons
-                for _ in 0..health_bar.health {
-                    parent
-                        .spawn(Node {
-                            width: Val::Px(30.),
-                            height: Val::Px(30.),
-                            ..default()
-                        })
-                        .with_child(ImageNode {
-                            image: icons_assets.heart_icon.clone(),
-                            ..default()
-                        });
-                }
+            hearts_container_commands.with_children(|parent| {
+                spawn_hearts(parent, health_bar, &icons_assets);
             });
         }
     }
 }
 
-fn continue_taking_damage(
-    mut query: Query<
-        (
-            Entity,
-            &mut HealthBar,
-            &ContinueTakingDamage,
-            Option<&AudioPlayer>,
-        ),
-        With<PlayerEntity>,
-    >,
+/// Spawns a heart for every slot of `health_bar`, missing health is shown as darkened hearts
+fn spawn_hearts(parent: &mut ChildBuilder, health_bar: &HealthBar, icons_assets: &IconsAssets) {
+    for slot in 0..health_bar.max.max(health_bar.health) {
+        let color = if slot < health_bar.health {
+            Color::WHITE
+        } else {
+            // hsla(0, 0%, 0%, 50%)
+            Color::hsla(0., 0., 0., 0.5)
+        };
+
+        parent
+            .spawn(Node {
+                width: Val::Px(30.),
+                height: Val::Px(30.),
+                ..default()
+            })
+            .with_child(ImageNode {
+                image: icons_assets.heart_icon.clone(),
+                color,
+                ..default()
+            });
+    }
+}
+
+fn update_invulnerability(
+    mut query: Query<(Entity, &mut Invulnerable, &mut Sprite), With<PlayerEntity>>,
+    time: Res<Time>,
     mut commands: Commands,
-    audio_assets: Res<AudioAssets>,
 ) {
-    for (entity, mut health_bar, damage_count, audio_player) in &mut query {
-        if health_bar.health > damage_count.0 {
-            if audio_player.is_none() {
-                commands.entity(entity).insert((
-                    AudioPlayer(audio_assets.damage.clone()),
-                    PlaybackSettings::REMOVE,
-                ));
-            }
-            health_bar.health -= 1;
+    for (entity, mut invulnerable, mut sprite) in &mut query {
+        invulnerable.0.tick(time.delta());
+
+        if invulnerable.0.finished() {
+            sprite.color.set_alpha(1.);
+            commands.entity(entity).remove::<Invulnerable>();
         } else {
-            health_bar.health = 0;
+            // Flash 10 times per second
+            let is_visible = (invulnerable.0.elapsed_secs() * 10.) as u32 % 2 == 1;
+            sprite.color.set_alpha(if is_visible { 1. } else { 0.3 });
         }
     }
 }
@@ -299,6 +700,7 @@ fn add_blindness(
         commands.spawn((
             AudioPlayer(audio_assets.i_can_feel_it_coming.clone()),
             PlaybackSettings::REMOVE,
+            BackgroundMusic,
         ));
 
         for main_camera in &main_camera_query {
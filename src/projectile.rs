@@ -0,0 +1,186 @@
+use crate::{
+    checkpoint::RespawnAtCheckpointEvent,
+    hostile_entity::{damage_player, DamageCount, HostileEntity},
+    level_manager::CurrentLevelInfo,
+    player::PlayerEntity,
+    screens::game_over_screen::RestartGameEvent,
+    utils::Maybe,
+    AutoDespawn, GameState, GRID_SIZE,
+};
+use bevy::prelude::*;
+use bevy::utils::Duration;
+use bevy_ecs_ldtk::{ldtk::ldtk_fields::LdtkFieldsError, prelude::*};
+use bevy_light_2d::prelude::PointLight2d;
+use bevy_rapier2d::prelude::*;
+
+/// Hostiles with a [`Caster`] fire projectiles at the player, the projectiles hurt
+/// like the hostile that fired them
+pub struct ProjectilePlugin;
+
+impl Plugin for ProjectilePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (cast_projectiles, projectile_hits.after(damage_player))
+                .run_if(in_state(GameState::PlayingScreen)),
+        )
+        .add_systems(Update, despawn_projectiles);
+    }
+}
+
+/// Seconds a projectile flies before it fades away
+const PROJECTILE_LIFETIME: u64 = 4;
+
+/// Set through the `casts_projectiles` LDtk field
+#[derive(Component, Debug)]
+pub struct Caster {
+    pub cooldown: Timer,
+    /// Pixels
+    pub range: f32,
+    /// Pixels per second
+    pub projectile_speed: f32,
+}
+
+impl Default for Caster {
+    fn default() -> Self {
+        Self {
+            cooldown: Timer::from_seconds(2., TimerMode::Repeating),
+            range: GRID_SIZE as f32 * 12.,
+            projectile_speed: 120.,
+        }
+    }
+}
+
+impl From<&EntityInstance> for Maybe<Caster> {
+    fn from(value: &EntityInstance) -> Self {
+        // Only some hostiles define the field
+        let casts_projectiles = match value.get_bool_field("casts_projectiles") {
+            Ok(casts_projectiles) => *casts_projectiles,
+            Err(LdtkFieldsError::FieldNotFound { .. }) => false,
+            Err(err) => panic!("casts_projectiles of {}: {}", value.identifier, err),
+        };
+
+        if casts_projectiles {
+            return Self::new(Caster::default());
+        }
+        Self::NONE
+    }
+}
+
+#[derive(Default, Component)]
+pub struct ProjectileEntity;
+
+fn cast_projectiles(
+    player_query: Query<&GlobalTransform, With<PlayerEntity>>,
+    mut caster_query: Query<(Entity, &GlobalTransform, &DamageCount, &mut Caster)>,
+    rapier_context: ReadRapierContext,
+    time: Res<Time>,
+    mut commands: Commands,
+) {
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
+    let player_position = player_transform.translation().truncate();
+    let rapier_context = rapier_context.single();
+
+    for (caster_entity, caster_transform, damage_count, mut caster) in &mut caster_query {
+        caster.cooldown.tick(time.delta());
+        if !caster.cooldown.just_finished() {
+            continue;
+        }
+
+        let caster_position = caster_transform.translation().truncate();
+        let to_player = player_position - caster_position;
+
+        // Don't waste projectiles on walls between the caster and the player
+        let can_see_player = to_player.length() <= caster.range
+            && rapier_context
+                .cast_ray(
+                    caster_position,
+                    to_player,
+                    1.,
+                    true,
+                    QueryFilter::only_fixed()
+                        .exclude_sensors()
+                        .exclude_collider(caster_entity),
+                )
+                .is_none();
+
+        if !can_see_player {
+            continue;
+        }
+
+        log::info!("Hostile cast a projectile");
+        commands.spawn((
+            // hsl(280, 80%, 65%)
+            Sprite::from_color(Color::hsl(280., 0.8, 0.65), Vec2::splat(6.)),
+            Transform::from_translation(caster_transform.translation()),
+            RigidBody::Dynamic,
+            GravityScale(0.),
+            Collider::ball(3.),
+            Sensor,
+            ActiveEvents::COLLISION_EVENTS,
+            Velocity::linear(to_player.normalize_or_zero() * caster.projectile_speed),
+            PointLight2d {
+                // hsl(280, 80%, 65%)
+                color: Color::hsl(280., 0.8, 0.65),
+                intensity: 0.6,
+                radius: GRID_SIZE as f32 * 1.5,
+                ..default()
+            },
+            // Damage is dealt by `hostile_entity::damage_player` like for any hostile
+            HostileEntity,
+            DamageCount(damage_count.0),
+            ProjectileEntity,
+            AutoDespawn::new(Duration::from_secs(PROJECTILE_LIFETIME)),
+        ));
+    }
+}
+
+/// Projectiles are gone once they hit the player or a wall
+fn projectile_hits(
+    mut collision_events: EventReader<CollisionEvent>,
+    projectile_query: Query<Entity, With<ProjectileEntity>>,
+    player_query: Query<Entity, With<PlayerEntity>>,
+    wall_query: Query<&RigidBody, Without<Sensor>>,
+    mut commands: Commands,
+) {
+    for collision in collision_events.read() {
+        let CollisionEvent::Started(entity_one, entity_two, ..) = *collision else {
+            continue;
+        };
+
+        let (projectile, other_entity) = if projectile_query.contains(entity_one) {
+            (entity_one, entity_two)
+        } else if projectile_query.contains(entity_two) {
+            (entity_two, entity_one)
+        } else {
+            continue;
+        };
+
+        let hit_wall = wall_query
+            .get(other_entity)
+            .is_ok_and(|rigid_body| *rigid_body == RigidBody::Fixed);
+
+        if hit_wall || player_query.contains(other_entity) {
+            commands.entity(projectile).despawn_recursive();
+        }
+    }
+}
+
+/// Projectiles in flight don't carry over to a restarted or new level
+fn despawn_projectiles(
+    current_level_info: Res<CurrentLevelInfo>,
+    mut restart_game_events: EventReader<RestartGameEvent>,
+    mut respawn_events: EventReader<RespawnAtCheckpointEvent>,
+    projectile_query: Query<Entity, With<ProjectileEntity>>,
+    mut commands: Commands,
+) {
+    let restarted = restart_game_events.read().count() > 0 || respawn_events.read().count() > 0;
+
+    if current_level_info.is_changed() || restarted {
+        for projectile in &projectile_query {
+            commands.entity(projectile).despawn_recursive();
+        }
+    }
+}
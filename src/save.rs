@@ -0,0 +1,235 @@
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+/// Data that is stored on disk (native) or in `localStorage` (wasm) between game sessions.
+///
+/// Every type gets its own save slot so that a corrupt slot only resets that part of the
+/// player's progress.
+pub trait SaveData: Serialize + DeserializeOwned + Default {
+    /// File name (without extension) on native and the `localStorage` key suffix on wasm
+    const KEY: &'static str;
+    /// Must be bumped whenever the layout changes in a non backward compatible way.
+    /// Save data with a different version is discarded and replaced by [`Default`].
+    const VERSION: u32;
+}
+
+/// On-disk representation of every save slot
+///
+/// ## Example
+///
+/// ```ron
+/// (
+///     version: 1,
+///     data: (
+///         times: {
+///             1: (secs: 25, nanos: 0),
+///         },
+///     ),
+/// )
+/// ```
+#[derive(Serialize, Deserialize)]
+struct SaveFile<T> {
+    version: u32,
+    data: T,
+}
+
+/// Why a save slot couldn't be used
+#[derive(Debug)]
+enum LoadError {
+    Missing,
+    Corrupt(ron::error::SpannedError),
+    Version { found: u32, expected: u32 },
+}
+
+impl std::fmt::Display for LoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LoadError::Missing => write!(f, "no save data found"),
+            LoadError::Corrupt(err) => write!(f, "save data is corrupt: {err}"),
+            LoadError::Version { found, expected } => {
+                write!(
+                    f,
+                    "save data has version {found} but {expected} was expected"
+                )
+            }
+        }
+    }
+}
+
+/// Loads the save slot of `T`, falling back to [`Default`] when it is missing, corrupt or
+/// was written by an incompatible version.
+pub fn load<T: SaveData>() -> T {
+    let contents = backend::read(T::KEY);
+
+    match parse::<T>(contents.as_deref()) {
+        Ok(data) => data,
+        Err(LoadError::Missing) => {
+            log::info!("No save data found for `{}`, using defaults", T::KEY);
+            T::default()
+        }
+        Err(err) => {
+            log::error!("Can't load `{}`, using defaults: {}", T::KEY, err);
+            if let Some(contents) = &contents {
+                backend::discard(T::KEY, contents);
+            }
+            T::default()
+        }
+    }
+}
+
+fn parse<T: SaveData>(contents: Option<&str>) -> Result<T, LoadError> {
+    let contents = contents.ok_or(LoadError::Missing)?;
+    let save_file = ron::from_str::<SaveFile<T>>(contents).map_err(LoadError::Corrupt)?;
+
+    if save_file.version != T::VERSION {
+        return Err(LoadError::Version {
+            found: save_file.version,
+            expected: T::VERSION,
+        });
+    }
+
+    Ok(save_file.data)
+}
+
+/// Writes `data` to the save slot of `T`, errors are logged as the game can continue without
+/// persisting.
+pub fn store<T: SaveData>(data: &T) {
+    let save_file = SaveFile {
+        version: T::VERSION,
+        data,
+    };
+
+    match ron::ser::to_string_pretty(&save_file, ron::ser::PrettyConfig::default()) {
+        Ok(contents) => {
+            if let Err(err) = backend::write(T::KEY, &contents) {
+                log::error!("Failed to save `{}`: {}", T::KEY, err);
+            }
+        }
+        Err(err) => log::error!("Failed to serialize `{}`: {}", T::KEY, err),
+    }
+}
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+mod backend {
+    use std::{fs, io, path::PathBuf};
+
+    fn save_dir() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join("shadow-runner"))
+    }
+
+    fn save_path(key: &str) -> Option<PathBuf> {
+        save_dir().map(|dir| dir.join(format!("{key}.ron")))
+    }
+
+    pub fn read(key: &str) -> Option<String> {
+        fs::read_to_string(save_path(key)?).ok()
+    }
+
+    pub fn write(key: &str, contents: &str) -> io::Result<()> {
+        let path = save_path(key)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "No data directory"))?;
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        // Write to a temporary file first, so that a crash mid-write never
+        // leaves a half written save file behind
+        let tmp_path = path.with_extension("ron.tmp");
+        fs::write(&tmp_path, contents)?;
+        fs::rename(tmp_path, path)
+    }
+
+    /// Moves unusable save data out of the way, so that it can be inspected later
+    pub fn discard(key: &str, _contents: &str) {
+        let Some(path) = save_path(key) else {
+            return;
+        };
+
+        if let Err(err) = fs::rename(&path, path.with_extension("ron.bak")) {
+            log::error!("Failed to back up save data for `{}`: {}", key, err);
+        }
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+mod backend {
+    use std::io;
+    use web_sys::Storage;
+
+    fn storage() -> Option<Storage> {
+        web_sys::window()?.local_storage().ok()?
+    }
+
+    fn storage_key(key: &str) -> String {
+        format!("shadow-runner.{key}")
+    }
+
+    pub fn read(key: &str) -> Option<String> {
+        storage()?.get_item(&storage_key(key)).ok()?
+    }
+
+    pub fn write(key: &str, contents: &str) -> io::Result<()> {
+        storage()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "localStorage unavailable"))?
+            .set_item(&storage_key(key), contents)
+            .map_err(|err| io::Error::other(format!("{err:?}")))
+    }
+
+    /// Moves unusable save data out of the way, so that it can be inspected later
+    pub fn discard(key: &str, contents: &str) {
+        let Some(storage) = storage() else {
+            return;
+        };
+
+        let key = storage_key(key);
+        let _ = storage.set_item(&format!("{key}.bak"), contents);
+        let _ = storage.remove_item(&key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize, Deserialize, Default, Debug, PartialEq)]
+    struct TestData {
+        value: u32,
+    }
+
+    impl SaveData for TestData {
+        const KEY: &'static str = "test_data";
+        const VERSION: u32 = 2;
+    }
+
+    #[test]
+    fn parses_current_version() {
+        let data = parse::<TestData>(Some("(version: 2, data: (value: 7))"));
+        assert_eq!(data.unwrap(), TestData { value: 7 });
+    }
+
+    #[test]
+    fn rejects_missing_save() {
+        assert!(matches!(parse::<TestData>(None), Err(LoadError::Missing)));
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        for contents in ["", "not a save file", "(version: 2, data: (value: \"7\"))"] {
+            assert!(matches!(
+                parse::<TestData>(Some(contents)),
+                Err(LoadError::Corrupt(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn rejects_other_version() {
+        assert!(matches!(
+            parse::<TestData>(Some("(version: 1, data: (value: 7))")),
+            Err(LoadError::Version {
+                found: 1,
+                expected: 2
+            })
+        ));
+    }
+}
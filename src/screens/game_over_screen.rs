@@ -2,6 +2,7 @@ use super::{despawn_screen, MainMenuButton};
 use crate::{
     assets::*,
     camera::MainCamera,
+    checkpoint::{ActiveCheckpoint, RespawnAtCheckpointEvent},
     time::{convert_time_to_text, spawn_best_time, RestartTimeEvent, TimeTakenRes},
     GameState,
 };
@@ -25,6 +26,10 @@ impl Plugin for GameOverPlugin {
                     in_state(GameState::GameOverScreen).or(in_state(GameState::PauseScreen)),
                 ),
             )
+            .add_systems(
+                Update,
+                respawn_at_checkpoint_button.run_if(in_state(GameState::GameOverScreen)),
+            )
             .add_systems(
                 Update,
                 restart_game_event.run_if(in_state(AssetsLoadingState::Loaded)),
@@ -38,12 +43,16 @@ pub struct OnGameOverScreen;
 #[derive(Component)]
 pub struct RestartGameButton;
 
+#[derive(Component)]
+pub struct RespawnAtCheckpointButton;
+
 // TODO: Hover Effect
 fn spawn_screen(
     mut commands: Commands,
     font_assets: Res<FontAssets>,
     icon_assets: Res<IconsAssets>,
     time_taken_res: Res<TimeTakenRes>,
+    active_checkpoint: Res<ActiveCheckpoint>,
 ) {
     let font = &font_assets.default_font;
     let reset_icon = &icon_assets.reset_icon;
@@ -104,6 +113,33 @@ fn spawn_screen(
                     ..default()
                 })
                 .with_children(|parent| {
+                    // Spawn Respawn At Checkpoint Button
+                    if active_checkpoint.0.is_some() {
+                        parent
+                            .spawn((
+                                RespawnAtCheckpointButton,
+                                Button,
+                                Node {
+                                    width: Val::Px(300.),
+                                    height: Val::Px(100.),
+                                    align_items: AlignItems::Center,
+                                    justify_content: JustifyContent::Center,
+                                    ..default()
+                                },
+                                BackgroundColor(Color::hsl(327., 0.24, 0.16)),
+                            ))
+                            .with_child((
+                                Text::new("Checkpoint"),
+                                // hsl(0, 0%, 88%)
+                                TextColor(Color::hsl(0., 0., 0.88)),
+                                TextFont {
+                                    font: font.clone(),
+                                    font_size: 33.,
+                                    ..default()
+                                },
+                            ));
+                    }
+
                     // Spawn Restart Button
                     parent
                         .spawn((
@@ -194,6 +230,17 @@ fn restart_game(
     }
 }
 
+fn respawn_at_checkpoint_button(
+    query: Query<&Interaction, (With<RespawnAtCheckpointButton>, Changed<Interaction>)>,
+    mut respawn_at_checkpoint_event: EventWriter<RespawnAtCheckpointEvent>,
+) {
+    for interaction in &query {
+        if Interaction::Pressed == *interaction {
+            respawn_at_checkpoint_event.send(RespawnAtCheckpointEvent);
+        }
+    }
+}
+
 #[derive(Event)]
 pub struct RestartGameEvent;
 
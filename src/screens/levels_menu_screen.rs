@@ -1,7 +1,7 @@
 use super::{despawn_screen, game_over_screen::RestartGameEvent, MainMenuButton};
 use crate::{
     assets::{self, FontAssets},
-    level_manager::{AllLevels, CurrentLevelInfo},
+    level_manager::{AllLevels, CurrentLevelInfo, LevelProgress},
     time::{spawn_best_time, TimeTakenRes},
     GameState,
 };
@@ -37,6 +37,7 @@ fn spawn_screen(
     font_assets: Res<FontAssets>,
     world: Res<assets::World>,
     time_taken_res: Res<TimeTakenRes>,
+    level_progress: Res<LevelProgress>,
 ) {
     let font = &font_assets.default_font;
 
@@ -139,6 +140,15 @@ fn spawn_screen(
                 })
                 .with_children(|parent| {
                     for level in &all_levels.all_levels {
+                        let is_unlocked = level_progress.is_unlocked(*level.0);
+
+                        let (background_color, text_color) = if is_unlocked {
+                            (Color::hsl(327., 0.24, 0.16), Color::WHITE)
+                        } else {
+                            // hsla(0, 0%, 16%, 60%)
+                            (Color::hsla(0., 0., 0.16, 0.6), Color::hsl(0., 0., 0.5))
+                        };
+
                         parent
                             .spawn((
                                 Button,
@@ -148,19 +158,38 @@ fn spawn_screen(
                                     height: Val::Px(100.),
                                     align_items: AlignItems::Center,
                                     justify_content: JustifyContent::Center,
+                                    flex_direction: FlexDirection::Column,
+                                    row_gap: Val::Px(5.),
                                     ..default()
                                 },
-                                BackgroundColor(Color::hsl(327., 0.24, 0.16)),
+                                BackgroundColor(background_color),
                             ))
-                            .with_child((
-                                Text::new(format!("{}", level.0 + 1)),
-                                TextColor(Color::WHITE),
-                                TextFont {
-                                    font: font.clone(),
-                                    font_size: 33.,
-                                    ..default()
-                                },
-                            ));
+                            .with_children(|parent| {
+                                parent.spawn((
+                                    Text::new(format!("{}", level.0 + 1)),
+                                    TextColor(text_color),
+                                    TextFont {
+                                        font: font.clone(),
+                                        font_size: 33.,
+                                        ..default()
+                                    },
+                                ));
+
+                                if time_taken_res.collectible_total(*level.0) > 0 {
+                                    parent.spawn((
+                                        Text::new(format!(
+                                            "{} collected",
+                                            time_taken_res.collection_progress_text(*level.0)
+                                        )),
+                                        TextColor(text_color),
+                                        TextFont {
+                                            font: font.clone(),
+                                            font_size: 12.,
+                                            ..default()
+                                        },
+                                    ));
+                                }
+                            });
                     }
                 });
         });
@@ -179,11 +208,17 @@ fn spawn_screen(
 fn choose_level(
     button_query: Query<(&Interaction, &LevelButton), (With<Button>, Changed<Interaction>)>,
     mut current_level_info: ResMut<CurrentLevelInfo>,
+    level_progress: Res<LevelProgress>,
     mut next_game_state: ResMut<NextState<GameState>>,
     mut restart_game_event: EventWriter<RestartGameEvent>,
 ) {
     for (interaction, level_button) in &button_query {
         if Interaction::Pressed == *interaction {
+            if !level_progress.is_unlocked(level_button.level_id) {
+                log::info!("Level {} is locked", level_button.level_id);
+                continue;
+            }
+
             next_game_state.set(GameState::PlayingScreen);
             if current_level_info.current_level_id == level_button.level_id {
                 restart_game_event.send(RestartGameEvent);
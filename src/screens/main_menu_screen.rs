@@ -1,4 +1,4 @@
-use super::{despawn_screen, LevelsMenuButton};
+use super::{despawn_screen, LevelsMenuButton, SettingsMenuButton};
 use crate::{
     assets::{self, FontAssets},
     GameState,
@@ -106,6 +106,31 @@ fn spawn_screen(mut commands: Commands, font_assets: Res<FontAssets>, world: Res
                     },
                 ));
 
+            // Spawn Settings Button
+            parent
+                .spawn((
+                    Button,
+                    SettingsMenuButton,
+                    Node {
+                        width: Val::Px(250.),
+                        height: Val::Px(100.),
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::Center,
+                        ..default()
+                    },
+                    BackgroundColor(Color::hsl(327., 0.24, 0.16)),
+                ))
+                .with_child((
+                    Text::new("Settings"),
+                    // hsl(0, 0%, 88%)
+                    TextColor(Color::hsl(0., 0., 0.88)),
+                    TextFont {
+                        font: font.clone(),
+                        font_size: 33.,
+                        ..default()
+                    },
+                ));
+
             // Spawn Exit Game Button
             parent
                 .spawn((
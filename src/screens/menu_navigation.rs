@@ -0,0 +1,183 @@
+use crate::input::STICK_DEADZONE;
+use bevy::prelude::*;
+use bevy::ui::UiSystem;
+
+/// Lets every [`Button`] be focused and pressed with a gamepad, focus is moved with the
+/// d-pad or left stick and `A` (South) presses the focused button.
+///
+/// Pressing sets [`Interaction::Pressed`], so the systems of each screen don't need to know
+/// about gamepads.
+pub struct MenuNavigationPlugin;
+
+impl Plugin for MenuNavigationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PreUpdate,
+            (release_pressed_buttons, navigate_menu, press_focused_button)
+                .chain()
+                .after(UiSystem::Focus),
+        )
+        .add_systems(Update, highlight_focused_button);
+    }
+}
+
+/// Button that is currently selected by the gamepad
+#[derive(Component)]
+pub struct Focused;
+
+/// Buttons pressed by [`press_focused_button`], they are released on the next frame
+/// similar to a mouse click
+#[derive(Component)]
+struct GamepadPressed;
+
+fn release_pressed_buttons(
+    mut query: Query<(Entity, &mut Interaction), With<GamepadPressed>>,
+    mut commands: Commands,
+) {
+    for (entity, mut interaction) in &mut query {
+        interaction.set_if_neq(Interaction::None);
+        commands.entity(entity).remove::<GamepadPressed>();
+    }
+}
+
+fn navigate_menu(
+    gamepads: Query<&Gamepad>,
+    buttons: Query<(Entity, &GlobalTransform, &InheritedVisibility), With<Button>>,
+    focused_query: Query<Entity, With<Focused>>,
+    mut previous_stick_direction: Local<IVec2>,
+    mut commands: Commands,
+) {
+    let mut direction = IVec2::ZERO;
+    let mut stick_direction = IVec2::ZERO;
+
+    for gamepad in &gamepads {
+        let stick = gamepad.left_stick();
+        if stick.length() > STICK_DEADZONE {
+            // UI coordinates grow downwards
+            stick_direction = if stick.x.abs() > stick.y.abs() {
+                IVec2::new(stick.x.signum() as i32, 0)
+            } else {
+                IVec2::new(0, -stick.y.signum() as i32)
+            };
+        }
+
+        if gamepad.just_pressed(GamepadButton::DPadUp) {
+            direction = IVec2::NEG_Y;
+        } else if gamepad.just_pressed(GamepadButton::DPadDown) {
+            direction = IVec2::Y;
+        } else if gamepad.just_pressed(GamepadButton::DPadLeft) {
+            direction = IVec2::NEG_X;
+        } else if gamepad.just_pressed(GamepadButton::DPadRight) {
+            direction = IVec2::X;
+        }
+    }
+
+    // The stick only moves the focus once per tilt
+    if stick_direction != *previous_stick_direction && stick_direction != IVec2::ZERO {
+        direction = stick_direction;
+    }
+    *previous_stick_direction = stick_direction;
+
+    if direction == IVec2::ZERO {
+        return;
+    }
+
+    let visible_buttons = visible_buttons(&buttons);
+
+    let focused = focused_query
+        .iter()
+        .find_map(|entity| visible_buttons.iter().find(|(e, _)| *e == entity))
+        .copied();
+
+    let next_focus = match focused {
+        Some((_, focused_position)) => {
+            let direction = direction.as_vec2();
+
+            // Pick the closest button in the direction, buttons that are not in line
+            // with the focused button are considered further away
+            visible_buttons
+                .iter()
+                .filter_map(|(entity, position)| {
+                    let offset = *position - focused_position;
+                    let along = offset.dot(direction);
+                    let across = offset.perp_dot(direction).abs();
+
+                    (along > 1.).then_some((*entity, along + across * 2.))
+                })
+                .min_by(|(_, a), (_, b)| a.total_cmp(b))
+                .map(|(entity, _)| entity)
+        }
+        None => first_button(&visible_buttons),
+    };
+
+    if let Some(next_focus) = next_focus {
+        for entity in &focused_query {
+            commands.entity(entity).remove::<Focused>();
+        }
+        commands.entity(next_focus).insert(Focused);
+    }
+}
+
+fn visible_buttons(
+    buttons: &Query<(Entity, &GlobalTransform, &InheritedVisibility), With<Button>>,
+) -> Vec<(Entity, Vec2)> {
+    buttons
+        .iter()
+        .filter(|(.., visibility)| visibility.get())
+        .map(|(entity, transform, _)| (entity, transform.translation().truncate()))
+        .collect()
+}
+
+/// Top-left most button
+fn first_button(buttons: &[(Entity, Vec2)]) -> Option<Entity> {
+    buttons
+        .iter()
+        .min_by(|(_, a), (_, b)| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)))
+        .map(|(entity, _)| *entity)
+}
+
+fn press_focused_button(
+    gamepads: Query<&Gamepad>,
+    mut focused_query: Query<(Entity, &mut Interaction), With<Focused>>,
+    buttons: Query<(Entity, &GlobalTransform, &InheritedVisibility), With<Button>>,
+    mut commands: Commands,
+) {
+    if !gamepads
+        .iter()
+        .any(|gamepad| gamepad.just_pressed(GamepadButton::South))
+    {
+        return;
+    }
+
+    let Ok((entity, mut interaction)) = focused_query.get_single_mut() else {
+        // Nothing focused yet, so the first press only focuses a button
+        if let Some(first_button) = first_button(&visible_buttons(&buttons)) {
+            commands.entity(first_button).insert(Focused);
+        }
+        return;
+    };
+
+    *interaction = Interaction::Pressed;
+    commands.entity(entity).insert(GamepadPressed);
+}
+
+fn highlight_focused_button(
+    added_query: Query<Entity, Added<Focused>>,
+    mut removed: RemovedComponents<Focused>,
+    mut commands: Commands,
+) {
+    for entity in removed.read() {
+        if let Some(mut entity_commands) = commands.get_entity(entity) {
+            entity_commands.remove::<Outline>();
+        }
+    }
+
+    for entity in &added_query {
+        commands.entity(entity).insert(Outline::new(
+            Val::Px(4.),
+            Val::Px(4.),
+            // hsl(0, 0%, 88%)
+            Color::hsl(0., 0., 0.88),
+        ));
+    }
+}
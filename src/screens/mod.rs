@@ -7,14 +7,18 @@ use credit_screen::CreditScreenPlugin;
 use game_over_screen::GameOverPlugin;
 use levels_menu_screen::LevelsMenuPlugin;
 use main_menu_screen::MainMenuPlugin;
+use menu_navigation::MenuNavigationPlugin;
 use pause_screen::PausePlugin;
+use settings_screen::{SettingsReturnState, SettingsScreenPlugin};
 
 pub mod credit_screen;
 pub mod game_over_screen;
 pub mod levels_menu_screen;
 pub mod loading_screen;
 pub mod main_menu_screen;
+pub mod menu_navigation;
 pub mod pause_screen;
+pub mod settings_screen;
 
 pub struct ScreensPlugin;
 
@@ -25,9 +29,16 @@ impl Plugin for ScreensPlugin {
             .add_plugins(GameOverPlugin)
             .add_plugins(LevelsMenuPlugin)
             .add_plugins(CreditScreenPlugin)
+            .add_plugins(SettingsScreenPlugin)
+            .add_plugins(MenuNavigationPlugin)
             .add_systems(
                 Update,
-                (button_hover, back_to_main_menu, back_to_levels_menu)
+                (
+                    button_hover,
+                    back_to_main_menu,
+                    back_to_levels_menu,
+                    open_settings_menu,
+                )
                     .run_if(in_state(AssetsLoadingState::Loaded)),
             )
             .add_systems(OnExit(GameState::PlayingScreen), pause_game)
@@ -41,6 +52,9 @@ pub struct MainMenuButton;
 #[derive(Component)]
 pub struct LevelsMenuButton;
 
+#[derive(Component)]
+pub struct SettingsMenuButton;
+
 /// Generic system that takes a component as a parameter, and will despawn all entities with that component
 pub fn despawn_screen<T: Component>(to_despawn: Query<Entity, With<T>>, mut commands: Commands) {
     for entity in &to_despawn {
@@ -90,6 +104,20 @@ pub fn back_to_levels_menu(
     }
 }
 
+pub fn open_settings_menu(
+    query: Query<&Interaction, (With<SettingsMenuButton>, Changed<Interaction>)>,
+    game_state: Res<State<GameState>>,
+    mut settings_return_state: ResMut<SettingsReturnState>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+) {
+    for interaction in &query {
+        if Interaction::Pressed == *interaction {
+            settings_return_state.0 = game_state.get().clone();
+            next_game_state.set(GameState::SettingsScreen);
+        }
+    }
+}
+
 pub fn pause_game(mut time: ResMut<Time<Virtual>>) {
     time.pause();
 }
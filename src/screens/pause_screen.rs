@@ -1,4 +1,6 @@
-use super::{despawn_screen, game_over_screen::RestartGameButton, MainMenuButton};
+use super::{
+    despawn_screen, game_over_screen::RestartGameButton, MainMenuButton, SettingsMenuButton,
+};
 use crate::{
     assets::{FontAssets, IconsAssets},
     time::{spawn_best_time, TimeTakenRes},
@@ -141,6 +143,32 @@ fn spawn_screen(
                             ));
                         });
 
+                    // Spawn Settings Button
+                    parent
+                        .spawn((
+                            SettingsMenuButton,
+                            Button,
+                            Node {
+                                width: Val::Px(300.),
+                                height: Val::Px(100.),
+                                align_items: AlignItems::Center,
+                                justify_content: JustifyContent::Center,
+                                column_gap: Val::Px(25.),
+                                ..default()
+                            },
+                            BackgroundColor(Color::hsl(327., 0.24, 0.16)),
+                        ))
+                        .with_child((
+                            Text::new("Settings"),
+                            // hsl(0, 0%, 88%)
+                            TextColor(Color::hsl(0., 0., 0.88)),
+                            TextFont {
+                                font: font.clone(),
+                                font_size: 33.,
+                                ..default()
+                            },
+                        ));
+
                     // Spawn Main Menu Button
                     parent
                         .spawn((
@@ -175,12 +203,12 @@ fn spawn_screen(
 
 fn resume_game_button(
     query: Query<&Interaction, (With<ResumeGameButton>, Changed<Interaction>)>,
-    mut input: ResMut<ButtonInput<KeyCode>>,
+    mut next_game_state: ResMut<NextState<GameState>>,
 ) {
     for interaction in &query {
         if Interaction::Pressed == *interaction {
             log::info!("Unpausing Game via Button");
-            input.press(KeyCode::Escape);
+            next_game_state.set(GameState::PlayingScreen);
         }
     }
 }
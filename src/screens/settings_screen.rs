@@ -0,0 +1,521 @@
+use super::despawn_screen;
+use crate::{
+    assets::{self, FontAssets},
+    input::{key_name, InputAction, InputBindings, Rebinding},
+    settings::{Settings, VOLUME_STEP},
+    GameState,
+};
+use bevy::prelude::*;
+
+pub struct SettingsScreenPlugin;
+
+impl Plugin for SettingsScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(SettingsReturnState(GameState::MainMenuScreen))
+            .add_systems(OnEnter(GameState::SettingsScreen), spawn_screen)
+            .add_systems(
+                OnExit(GameState::SettingsScreen),
+                (despawn_screen::<OnSettingsScreen>, cancel_rebinding),
+            )
+            .add_systems(
+                Update,
+                (
+                    (settings_button, update_settings_text).chain(),
+                    (rebind_button, update_binding_text).chain(),
+                )
+                    .run_if(in_state(GameState::SettingsScreen)),
+            );
+    }
+}
+
+/// The screen settings was opened from, i.e. Main Menu or Pause Screen
+#[derive(Resource)]
+pub struct SettingsReturnState(pub GameState);
+
+#[derive(Component)]
+pub struct OnSettingsScreen;
+
+#[derive(Component, Clone, Copy)]
+pub enum SettingsButton {
+    MusicVolumeDown,
+    MusicVolumeUp,
+    SfxVolumeDown,
+    SfxVolumeUp,
+    #[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), allow(dead_code))]
+    Fullscreen,
+    ViewportScaling,
+    Back,
+}
+
+/// Text that displays the current value of a setting
+#[derive(Component, Clone, Copy)]
+pub enum SettingsText {
+    MusicVolume,
+    SfxVolume,
+    #[cfg_attr(all(target_arch = "wasm32", target_os = "unknown"), allow(dead_code))]
+    Fullscreen,
+    ViewportScaling,
+}
+
+/// Starts listening for a new key for the action
+#[derive(Component, Clone, Copy)]
+pub struct RebindButton(pub InputAction);
+
+/// Text that displays the key bound to the action
+#[derive(Component, Clone, Copy)]
+pub struct BindingText(pub InputAction);
+
+impl SettingsText {
+    fn value(self, settings: &Settings) -> String {
+        match self {
+            SettingsText::MusicVolume => format!("{:.0}%", settings.music_volume * 100.),
+            SettingsText::SfxVolume => format!("{:.0}%", settings.sfx_volume * 100.),
+            SettingsText::Fullscreen => {
+                String::from(if settings.fullscreen { "On" } else { "Off" })
+            }
+            SettingsText::ViewportScaling => String::from(settings.viewport_scaling.label()),
+        }
+    }
+}
+
+fn spawn_screen(
+    mut commands: Commands,
+    font_assets: Res<FontAssets>,
+    world: Res<assets::World>,
+    settings: Res<Settings>,
+    bindings: Res<InputBindings>,
+) {
+    let font = &font_assets.default_font;
+
+    // Spawn Background
+    commands
+        .spawn((
+            Node {
+                width: Val::Vw(100.),
+                height: Val::Vh(100.),
+                position_type: PositionType::Absolute,
+                display: Display::Flex,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                top: Val::Px(0.),
+                left: Val::Px(0.),
+                ..default()
+            },
+            // hsl(213, 71%, 35%)
+            BackgroundColor(Color::hsl(213., 0.71, 0.35)),
+            OnSettingsScreen,
+        ))
+        .with_child(ImageNode {
+            image: world.background.clone(),
+            ..default()
+        });
+
+    commands
+        .spawn((
+            OnSettingsScreen,
+            Node {
+                width: Val::Percent(100.),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(30.),
+                position_type: PositionType::Absolute,
+                left: Val::Px(0.),
+                top: Val::Px(0.),
+                ..default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("Settings"),
+                TextColor(Color::hsl(327., 0.24, 0.16)),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 100.,
+                    ..default()
+                },
+            ));
+
+            parent
+                .spawn(Node {
+                    display: Display::Flex,
+                    align_items: AlignItems::FlexStart,
+                    column_gap: Val::Px(60.),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    // Spawn General Settings
+                    parent
+                        .spawn(Node {
+                            display: Display::Flex,
+                            flex_direction: FlexDirection::Column,
+                            row_gap: Val::Px(30.),
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            spawn_section_title(parent, font, "General");
+
+                            spawn_setting_row(
+                                parent,
+                                font,
+                                "Music Volume",
+                                SettingsText::MusicVolume,
+                                &settings,
+                                Some(SettingsButton::MusicVolumeDown),
+                                SettingsButton::MusicVolumeUp,
+                            );
+
+                            spawn_setting_row(
+                                parent,
+                                font,
+                                "Sound Effects",
+                                SettingsText::SfxVolume,
+                                &settings,
+                                Some(SettingsButton::SfxVolumeDown),
+                                SettingsButton::SfxVolumeUp,
+                            );
+
+                            // The browser decides if the canvas is fullscreen on wasm
+                            #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+                            spawn_setting_row(
+                                parent,
+                                font,
+                                "Fullscreen",
+                                SettingsText::Fullscreen,
+                                &settings,
+                                None,
+                                SettingsButton::Fullscreen,
+                            );
+
+                            spawn_setting_row(
+                                parent,
+                                font,
+                                "Scaling",
+                                SettingsText::ViewportScaling,
+                                &settings,
+                                None,
+                                SettingsButton::ViewportScaling,
+                            );
+                        });
+
+                    // Spawn Controls
+                    parent
+                        .spawn(Node {
+                            display: Display::Flex,
+                            flex_direction: FlexDirection::Column,
+                            row_gap: Val::Px(30.),
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            spawn_section_title(parent, font, "Controls");
+
+                            for action in InputAction::ALL {
+                                spawn_binding_row(parent, font, action, &bindings);
+                            }
+                        });
+                });
+
+            // Spawn Back Button
+            parent
+                .spawn((
+                    Button,
+                    SettingsButton::Back,
+                    Node {
+                        width: Val::Px(300.),
+                        height: Val::Px(100.),
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::Center,
+                        ..default()
+                    },
+                    BackgroundColor(Color::hsl(327., 0.24, 0.16)),
+                ))
+                .with_child((
+                    Text::new("Back"),
+                    // hsl(0, 0%, 88%)
+                    TextColor(Color::hsl(0., 0., 0.88)),
+                    TextFont {
+                        font: font.clone(),
+                        font_size: 33.,
+                        ..default()
+                    },
+                ));
+        });
+}
+
+/// Spawns `label  [-]  value  [+]`, settings without a decrease button
+/// are toggles and only have a single button around the value
+fn spawn_setting_row(
+    parent: &mut ChildBuilder,
+    font: &Handle<Font>,
+    label: &str,
+    settings_text: SettingsText,
+    settings: &Settings,
+    decrease_button: Option<SettingsButton>,
+    increase_button: SettingsButton,
+) {
+    parent
+        .spawn(Node {
+            width: Val::Px(520.),
+            display: Display::Flex,
+            justify_content: JustifyContent::SpaceBetween,
+            align_items: AlignItems::Center,
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(label),
+                TextColor(Color::hsl(327., 0.24, 0.16)),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 33.,
+                    ..default()
+                },
+            ));
+
+            parent
+                .spawn(Node {
+                    display: Display::Flex,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    column_gap: Val::Px(20.),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    let value_text = (
+                        Text::new(settings_text.value(settings)),
+                        // hsl(0, 0%, 88%)
+                        TextColor(Color::hsl(0., 0., 0.88)),
+                        TextFont {
+                            font: font.clone(),
+                            font_size: 33.,
+                            ..default()
+                        },
+                        settings_text,
+                    );
+
+                    let Some(decrease_button) = decrease_button else {
+                        // Toggle
+                        parent
+                            .spawn((
+                                Button,
+                                increase_button,
+                                Node {
+                                    width: Val::Px(250.),
+                                    height: Val::Px(60.),
+                                    align_items: AlignItems::Center,
+                                    justify_content: JustifyContent::Center,
+                                    ..default()
+                                },
+                                BackgroundColor(Color::hsl(327., 0.24, 0.16)),
+                            ))
+                            .with_child(value_text);
+                        return;
+                    };
+
+                    spawn_small_button(parent, font, "-", decrease_button);
+
+                    parent
+                        .spawn((
+                            Node {
+                                width: Val::Px(110.),
+                                height: Val::Px(60.),
+                                align_items: AlignItems::Center,
+                                justify_content: JustifyContent::Center,
+                                ..default()
+                            },
+                            BackgroundColor(Color::hsl(327., 0.24, 0.16)),
+                        ))
+                        .with_child(value_text);
+
+                    spawn_small_button(parent, font, "+", increase_button);
+                });
+        });
+}
+
+fn spawn_section_title(parent: &mut ChildBuilder, font: &Handle<Font>, title: &str) {
+    parent.spawn((
+        Text::new(title),
+        TextColor(Color::WHITE),
+        TextFont {
+            font: font.clone(),
+            font_size: 40.,
+            ..default()
+        },
+    ));
+}
+
+fn spawn_binding_row(
+    parent: &mut ChildBuilder,
+    font: &Handle<Font>,
+    action: InputAction,
+    bindings: &InputBindings,
+) {
+    parent
+        .spawn(Node {
+            width: Val::Px(450.),
+            display: Display::Flex,
+            justify_content: JustifyContent::SpaceBetween,
+            align_items: AlignItems::Center,
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(action.label()),
+                TextColor(Color::hsl(327., 0.24, 0.16)),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 33.,
+                    ..default()
+                },
+            ));
+
+            parent
+                .spawn((
+                    Button,
+                    RebindButton(action),
+                    Node {
+                        width: Val::Px(200.),
+                        height: Val::Px(60.),
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::Center,
+                        ..default()
+                    },
+                    BackgroundColor(Color::hsl(327., 0.24, 0.16)),
+                ))
+                .with_child((
+                    Text::new(binding_text(action, bindings, None)),
+                    // hsl(0, 0%, 88%)
+                    TextColor(Color::hsl(0., 0., 0.88)),
+                    TextFont {
+                        font: font.clone(),
+                        font_size: 33.,
+                        ..default()
+                    },
+                    BindingText(action),
+                ));
+        });
+}
+
+fn binding_text(
+    action: InputAction,
+    bindings: &InputBindings,
+    rebinding: Option<InputAction>,
+) -> String {
+    if rebinding == Some(action) {
+        return String::from("Press a key");
+    }
+
+    bindings
+        .key(action)
+        .map(key_name)
+        .unwrap_or(String::from("Unbound"))
+}
+
+fn spawn_small_button(
+    parent: &mut ChildBuilder,
+    font: &Handle<Font>,
+    text: &str,
+    settings_button: SettingsButton,
+) {
+    parent
+        .spawn((
+            Button,
+            settings_button,
+            Node {
+                width: Val::Px(60.),
+                height: Val::Px(60.),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            BackgroundColor(Color::hsl(327., 0.24, 0.16)),
+        ))
+        .with_child((
+            Text::new(text),
+            // hsl(0, 0%, 88%)
+            TextColor(Color::hsl(0., 0., 0.88)),
+            TextFont {
+                font: font.clone(),
+                font_size: 33.,
+                ..default()
+            },
+        ));
+}
+
+fn settings_button(
+    query: Query<(&Interaction, &SettingsButton), Changed<Interaction>>,
+    mut settings: ResMut<Settings>,
+    settings_return_state: Res<SettingsReturnState>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+) {
+    for (interaction, settings_button) in &query {
+        if Interaction::Pressed != *interaction {
+            continue;
+        }
+
+        match settings_button {
+            SettingsButton::MusicVolumeDown => {
+                settings.music_volume = (settings.music_volume - VOLUME_STEP).clamp(0., 1.);
+            }
+            SettingsButton::MusicVolumeUp => {
+                settings.music_volume = (settings.music_volume + VOLUME_STEP).clamp(0., 1.);
+            }
+            SettingsButton::SfxVolumeDown => {
+                settings.sfx_volume = (settings.sfx_volume - VOLUME_STEP).clamp(0., 1.);
+            }
+            SettingsButton::SfxVolumeUp => {
+                settings.sfx_volume = (settings.sfx_volume + VOLUME_STEP).clamp(0., 1.);
+            }
+            SettingsButton::Fullscreen => {
+                settings.fullscreen = !settings.fullscreen;
+            }
+            SettingsButton::ViewportScaling => {
+                settings.viewport_scaling = settings.viewport_scaling.next();
+            }
+            SettingsButton::Back => {
+                next_game_state.set(settings_return_state.0.clone());
+            }
+        }
+    }
+}
+
+fn update_settings_text(mut query: Query<(&mut Text, &SettingsText)>, settings: Res<Settings>) {
+    if settings.is_changed() {
+        for (mut text, settings_text) in &mut query {
+            text.0 = settings_text.value(&settings);
+        }
+    }
+}
+
+fn rebind_button(
+    query: Query<(&Interaction, &RebindButton), Changed<Interaction>>,
+    mut rebinding: ResMut<Rebinding>,
+) {
+    for (interaction, rebind_button) in &query {
+        if Interaction::Pressed == *interaction {
+            // Pressing the same button again cancels the rebinding
+            rebinding.0 = if rebinding.0 == Some(rebind_button.0) {
+                None
+            } else {
+                Some(rebind_button.0)
+            };
+        }
+    }
+}
+
+fn update_binding_text(
+    mut query: Query<(&mut Text, &BindingText)>,
+    bindings: Res<InputBindings>,
+    rebinding: Res<Rebinding>,
+) {
+    if bindings.is_changed() || rebinding.is_changed() {
+        for (mut text, binding_text_component) in &mut query {
+            text.0 = binding_text(binding_text_component.0, &bindings, rebinding.0);
+        }
+    }
+}
+
+fn cancel_rebinding(mut rebinding: ResMut<Rebinding>) {
+    rebinding.0 = None;
+}
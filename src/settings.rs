@@ -0,0 +1,130 @@
+use crate::save::{self, SaveData};
+use bevy::audio::Volume;
+use bevy::prelude::*;
+use bevy::transform::TransformSystem;
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+use bevy::window::WindowMode;
+use serde::{Deserialize, Serialize};
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(save::load::<Settings>())
+            .add_systems(
+                Update,
+                (
+                    update_playing_volume,
+                    #[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+                    apply_window_mode,
+                    persist_settings,
+                ),
+            )
+            // Audio sinks are created in `PostUpdate` after transform propagation, so
+            // the volume has to be adjusted before that to avoid a loud first frame
+            .add_systems(
+                PostUpdate,
+                set_initial_volume.before(TransformSystem::TransformPropagate),
+            );
+    }
+}
+
+pub const VOLUME_STEP: f32 = 0.1;
+
+#[derive(Resource, Serialize, Deserialize, Debug, Clone, PartialEq, better_default::Default)]
+#[default(music_volume: 1., sfx_volume: 1.)]
+pub struct Settings {
+    /// Volume of [`BackgroundMusic`] in range `0.0..=1.0`
+    pub music_volume: f32,
+    /// Volume of every other sound in range `0.0..=1.0`
+    pub sfx_volume: f32,
+    /// Ignored on wasm, as the canvas is managed by the browser
+    pub fullscreen: bool,
+    pub viewport_scaling: ViewportScaling,
+}
+
+impl SaveData for Settings {
+    const KEY: &'static str = "settings";
+    const VERSION: u32 = 1;
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ViewportScaling {
+    /// Keeps the game at 16:9 and fills the rest of the window with black bars
+    #[default]
+    Letterbox,
+    /// Fills the whole window, revealing more of the level on wider/taller screens
+    Fill,
+}
+
+impl ViewportScaling {
+    pub fn next(self) -> Self {
+        match self {
+            ViewportScaling::Letterbox => ViewportScaling::Fill,
+            ViewportScaling::Fill => ViewportScaling::Letterbox,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ViewportScaling::Letterbox => "Letterbox",
+            ViewportScaling::Fill => "Fill",
+        }
+    }
+}
+
+/// Marks an [`AudioPlayer`] as music, so that it uses [`Settings::music_volume`]
+/// instead of [`Settings::sfx_volume`]
+#[derive(Component, Default)]
+pub struct BackgroundMusic;
+
+impl Settings {
+    fn volume(&self, is_music: bool) -> f32 {
+        if is_music {
+            self.music_volume
+        } else {
+            self.sfx_volume
+        }
+    }
+}
+
+fn set_initial_volume(
+    mut query: Query<(&mut PlaybackSettings, Has<BackgroundMusic>), Added<PlaybackSettings>>,
+    settings: Res<Settings>,
+) {
+    for (mut playback_settings, is_music) in &mut query {
+        playback_settings.volume = Volume::new(settings.volume(is_music));
+    }
+}
+
+fn update_playing_volume(
+    query: Query<(&AudioSink, Has<BackgroundMusic>)>,
+    settings: Res<Settings>,
+) {
+    if settings.is_changed() {
+        for (audio_sink, is_music) in &query {
+            audio_sink.set_volume(settings.volume(is_music));
+        }
+    }
+}
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+fn apply_window_mode(settings: Res<Settings>, mut windows: Query<&mut Window>) {
+    if settings.is_changed() {
+        for mut window in &mut windows {
+            window.mode = if settings.fullscreen {
+                WindowMode::Fullscreen(MonitorSelection::Current)
+            } else {
+                WindowMode::Windowed
+            };
+        }
+    }
+}
+
+fn persist_settings(settings: Res<Settings>) {
+    // Settings are loaded while building the plugin, so there is nothing to save on the first frame
+    if settings.is_changed() && !settings.is_added() {
+        log::info!("Saving Settings");
+        save::store(&*settings);
+    }
+}
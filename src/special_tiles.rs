@@ -1,24 +1,42 @@
 use crate::{
-    assets::AudioAssets, colliders::ColliderBundle, hostile_entity::DamageCount,
-    player::PlayerEntity, sprite_animation::Animation, walls::GlobalWallEntity, GameState,
+    assets::{AudioAssets, IconsAssets},
+    colliders::ColliderBundle,
+    hostile_entity::DamageCount,
+    player::{DashState, JumpState, PlayerEntity, PlayerMovementConfig},
+    sprite_animation::Animation,
+    walls::GlobalWallEntity,
+    GameState, GRID_SIZE,
 };
-use bevy::prelude::*;
-use bevy_ecs_ldtk::{app::LdtkIntCellAppExt, LdtkIntCell};
+use bevy::{prelude::*, sprite::Anchor};
+use bevy_ecs_ldtk::{app::LdtkIntCellAppExt, prelude::*, LdtkIntCell};
 use bevy_rapier2d::prelude::*;
+use std::f32::consts::PI;
 
 pub struct SpecialTilesPlugin;
 
 impl Plugin for SpecialTilesPlugin {
     fn build(&self, app: &mut App) {
         app.register_ldtk_int_cell_for_layer::<JumpBooster>("Jump_Booster", 1)
+            .register_ldtk_entity::<LaunchPad>("Launch_Pad")
             .register_ldtk_int_cell_for_layer::<Spike>("Spike", 1)
             .add_systems(
                 Update,
-                jump_booster_collision_event.run_if(in_state(GameState::PlayingScreen)),
+                (
+                    spawn_jump_booster_sprites,
+                    jump_booster_collision_event,
+                    animate_jump_boosters,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::PlayingScreen)),
             );
     }
 }
 
+/// Seconds the launch animation of a jump booster takes
+const LAUNCH_ANIMATION_TIME: f32 = 0.3;
+
+/// Jump booster painted on the `Jump_Booster` IntGrid layer, it always uses the
+/// [`JumpBoosterEntity`] defaults
 #[derive(better_default::Default, Bundle, LdtkIntCell)]
 #[default(active_events: ActiveEvents::COLLISION_EVENTS)]
 pub struct JumpBooster {
@@ -28,13 +46,98 @@ pub struct JumpBooster {
     pub active_events: ActiveEvents,
 }
 
+/// Jump booster placed as an LDtk entity, so that it can be configured through the
+/// `strength`, `max_strength`, `angle` and `mode` fields
+#[derive(better_default::Default, Bundle, LdtkEntity)]
+#[default(
+    collider: Collider::cuboid(GRID_SIZE as f32 / 2., GRID_SIZE as f32 / 2.),
+    active_events: ActiveEvents::COLLISION_EVENTS
+)]
+pub struct LaunchPad {
+    pub collider: Collider,
+    #[from_entity_instance]
+    pub entity: JumpBoosterEntity,
+    pub active_events: ActiveEvents,
+}
+
+/// How a jump booster picks the launch speed, set by the LDtk `Booster_Mode` enum
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BoosterMode {
+    /// Always launches with `strength`
+    Fixed,
+    /// Adds the speed the player falls onto it with to `strength`, up to `max_strength`
+    #[default]
+    Trampoline,
+}
+
+impl From<&str> for BoosterMode {
+    fn from(value: &str) -> Self {
+        match value {
+            "Fixed" => BoosterMode::Fixed,
+            "Trampoline" => BoosterMode::Trampoline,
+            _ => panic!("Unsupported Booster_Mode {}", value),
+        }
+    }
+}
+
 #[derive(better_default::Default, Component)]
-#[default(boost_velocty: 500., boost_cap: 650.)]
+#[default(strength: 500., max_strength: 650., direction: Vec2::Y)]
 pub struct JumpBoosterEntity {
-    pub boost_velocty: f32,
-    pub boost_cap: f32,
+    /// Pixels per second along `direction`
+    pub strength: f32,
+    /// Only limits [`BoosterMode::Trampoline`]
+    pub max_strength: f32,
+    /// Unit vector the player is launched towards
+    pub direction: Vec2,
+    pub mode: BoosterMode,
+    /// Runs while the launch animation plays
+    pub launch_animation: Option<Timer>,
+}
+
+impl From<&EntityInstance> for JumpBoosterEntity {
+    fn from(value: &EntityInstance) -> Self {
+        let strength = *value
+            .get_float_field("strength")
+            .expect("strength field should be correctly typed");
+        let max_strength = *value
+            .get_float_field("max_strength")
+            .expect("max_strength field should be correctly typed");
+        // Degrees from straight up, positive angles lean to the right
+        let angle = *value
+            .get_float_field("angle")
+            .expect("angle field should be correctly typed");
+        let mode = value
+            .get_enum_field("mode")
+            .expect("mode field should be correctly typed");
+
+        Self {
+            strength,
+            max_strength,
+            direction: Vec2::from_angle(-angle.to_radians()).rotate(Vec2::Y),
+            mode: BoosterMode::from(mode.as_str()),
+            ..default()
+        }
+    }
+}
+
+impl JumpBoosterEntity {
+    /// Velocity of a player that hits the booster with `velocity`, the part of the
+    /// velocity across the launch direction is kept
+    fn launch_velocity(&self, velocity: Vec2) -> Vec2 {
+        let along = velocity.dot(self.direction);
+        let speed = match self.mode {
+            BoosterMode::Fixed => self.strength,
+            BoosterMode::Trampoline => (self.strength - along).min(self.max_strength),
+        };
+
+        velocity - self.direction * along + self.direction * speed
+    }
 }
 
+/// Drawn by the booster instead of the tile, so that it can be stretched when launching
+#[derive(Component)]
+pub struct JumpBoosterSprite;
+
 #[derive(better_default::Default, Bundle, LdtkIntCell)]
 #[default(
     damage_count: DamageCount(1),
@@ -54,35 +157,126 @@ pub struct Spike {
 #[derive(Default, Component)]
 pub struct SpikeEntity;
 
+/// Every jump booster gets a [`JumpBoosterSprite`], the ones of the `Jump_Booster`
+/// layer are drawn over their tile
+fn spawn_jump_booster_sprites(
+    mut jump_booster_query: Query<
+        (Entity, &mut Transform, &JumpBoosterEntity),
+        Added<JumpBoosterEntity>,
+    >,
+    icons_assets: Res<IconsAssets>,
+    mut commands: Commands,
+) {
+    for (entity, mut transform, jump_booster) in &mut jump_booster_query {
+        // Turns the sprite and collider of angled boosters towards their direction
+        transform.rotation = Quat::from_rotation_arc_2d(Vec2::Y, jump_booster.direction);
+
+        commands.entity(entity).with_child((
+            Sprite {
+                image: icons_assets.jump_booster_icon.clone(),
+                anchor: Anchor::BottomCenter,
+                ..default()
+            },
+            Transform::from_xyz(0., -GRID_SIZE as f32 / 2., 0.1),
+            JumpBoosterSprite,
+        ));
+    }
+}
+
+/// Launches the player off the jump booster it lands on, if the player touches several
+/// boosters at once the strongest one is used
+#[allow(clippy::type_complexity)]
 fn jump_booster_collision_event(
     mut collision_events: EventReader<CollisionEvent>,
-    mut player_query: Query<(Entity, &mut Velocity), With<PlayerEntity>>,
-    jump_booster_query: Query<(Entity, &JumpBoosterEntity)>,
+    mut player_query: Query<
+        (Entity, &mut Velocity, &mut JumpState, &mut DashState),
+        With<PlayerEntity>,
+    >,
+    mut jump_booster_query: Query<&mut JumpBoosterEntity>,
+    movement_config: Res<PlayerMovementConfig>,
     audio_assets: Res<AudioAssets>,
     mut commands: Commands,
 ) {
+    let Ok((player_entity, mut velocity, mut jump_state, mut dash_state)) =
+        player_query.get_single_mut()
+    else {
+        return;
+    };
+
+    let mut used_booster = None;
+
     for collision in collision_events.read() {
-        if let CollisionEvent::Started(entity_one, entity_two, ..) = *collision {
-            let (player_entity, mut player_velocty) = player_query.single_mut();
-
-            if entity_one == player_entity || entity_two == player_entity {
-                for (jump_booster_entity, jump_booster) in &jump_booster_query {
-                    if entity_two == jump_booster_entity || entity_one == jump_booster_entity {
-                        let mut new_velocity = jump_booster.boost_velocty - player_velocty.linvel.y;
-
-                        if new_velocity > jump_booster.boost_cap {
-                            new_velocity = jump_booster.boost_cap;
-                        }
-                        player_velocty.linvel.y = new_velocity;
-
-                        commands.spawn((
-                            AudioPlayer(audio_assets.jump_boost.clone()),
-                            PlaybackSettings::REMOVE,
-                        ));
-
-                        return;
-                    }
-                }
+        let CollisionEvent::Started(entity_one, entity_two, ..) = *collision else {
+            continue;
+        };
+
+        let other_entity = if entity_one == player_entity {
+            entity_two
+        } else if entity_two == player_entity {
+            entity_one
+        } else {
+            continue;
+        };
+
+        let Ok(jump_booster) = jump_booster_query.get(other_entity) else {
+            continue;
+        };
+
+        let is_stronger = used_booster
+            .and_then(|entity| jump_booster_query.get(entity).ok())
+            .is_none_or(|used| jump_booster.strength > used.strength);
+
+        if is_stronger {
+            used_booster = Some(other_entity);
+        }
+    }
+
+    let Some(mut jump_booster) =
+        used_booster.and_then(|entity| jump_booster_query.get_mut(entity).ok())
+    else {
+        return;
+    };
+
+    velocity.linvel = jump_booster.launch_velocity(velocity.linvel);
+    // The launch isn't cut short by releasing jump, and angled boosters aren't
+    // immediately steered back by the movement keys
+    jump_state.is_jumping = false;
+    if jump_booster.direction.x != 0. {
+        jump_state.control_lock = movement_config.launch_control_lock;
+    }
+    dash_state.time_left = 0.;
+
+    jump_booster.launch_animation =
+        Some(Timer::from_seconds(LAUNCH_ANIMATION_TIME, TimerMode::Once));
+
+    commands.spawn((
+        AudioPlayer(audio_assets.jump_boost.clone()),
+        PlaybackSettings::REMOVE,
+    ));
+}
+
+/// Stretches the booster sprite towards the launch direction and back
+fn animate_jump_boosters(
+    mut jump_booster_query: Query<(&mut JumpBoosterEntity, &Children)>,
+    mut sprite_query: Query<&mut Transform, With<JumpBoosterSprite>>,
+    time: Res<Time>,
+) {
+    for (mut jump_booster, children) in &mut jump_booster_query {
+        let Some(launch_animation) = &mut jump_booster.launch_animation else {
+            continue;
+        };
+
+        launch_animation.tick(time.delta());
+        let stretch = if launch_animation.finished() {
+            jump_booster.launch_animation = None;
+            1.
+        } else {
+            1. + (launch_animation.fraction() * PI).sin() * 0.6
+        };
+
+        for child in children {
+            if let Ok(mut transform) = sprite_query.get_mut(*child) {
+                transform.scale.y = stretch;
             }
         }
     }
@@ -0,0 +1,135 @@
+use crate::{
+    assets::AudioAssets,
+    hostile_entity::{damage_player, track_hostile_contacts, HostileEntity, Patrol},
+    player::{HostileContacts, JumpState, PlayerEntity, PlayerMovementConfig, PLAYER_HEIGHT},
+    projectile::Caster,
+    GameState, GRID_SIZE,
+};
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+/// Landing on a hostile from above defeats it, unless it is [`StompImmune`]. Defeated
+/// hostiles are gone until the level is restarted.
+pub struct StompPlugin;
+
+impl Plugin for StompPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                stomp_hostiles
+                    .after(track_hostile_contacts)
+                    .before(damage_player),
+                animate_defeat,
+            )
+                .run_if(in_state(GameState::PlayingScreen)),
+        );
+    }
+}
+
+/// Seconds the death animation of a stomped hostile takes
+const DEFEAT_ANIMATION_TIME: f32 = 0.4;
+/// How far the feet of the player may sink into the hostile and still count as a stomp
+const STOMP_TOLERANCE: f32 = 4.;
+
+/// Set through the `StompImmune` ability in `hostiles.ron`
+#[derive(Default, Component)]
+pub struct StompImmune;
+
+/// Stomped hostile that is playing its death animation
+#[derive(Component)]
+pub struct Defeated(pub Timer);
+
+#[allow(clippy::type_complexity)]
+fn stomp_hostiles(
+    mut player_query: Query<
+        (
+            &Transform,
+            &mut Velocity,
+            &mut HostileContacts,
+            &mut JumpState,
+        ),
+        With<PlayerEntity>,
+    >,
+    // Spikes and projectiles are hostile too, but only patrolling hostiles can be stomped
+    hostile_query: Query<
+        (&GlobalTransform, &Collider),
+        (With<HostileEntity>, With<Patrol>, Without<StompImmune>),
+    >,
+    movement_config: Res<PlayerMovementConfig>,
+    audio_assets: Res<AudioAssets>,
+    mut commands: Commands,
+) {
+    for (player_transform, mut velocity, mut hostile_contacts, mut jump_state) in &mut player_query
+    {
+        // The contact already stopped the fall, so the player only must not be rising
+        if velocity.linvel.y > 0. {
+            continue;
+        }
+
+        let feet = player_transform.translation.y - PLAYER_HEIGHT / 2.;
+
+        let stomped: Vec<Entity> = hostile_contacts
+            .0
+            .iter()
+            .copied()
+            .filter(|entity| {
+                hostile_query
+                    .get(*entity)
+                    .is_ok_and(|(hostile_transform, collider)| {
+                        let half_height = collider
+                            .as_cuboid()
+                            .map_or(GRID_SIZE as f32 / 2., |cuboid| cuboid.half_extents().y);
+                        let hostile_top = hostile_transform.translation().y + half_height;
+                        feet >= hostile_top - STOMP_TOLERANCE
+                    })
+            })
+            .collect();
+
+        if stomped.is_empty() {
+            continue;
+        }
+
+        for hostile_entity in stomped {
+            log::info!("Stomped hostile entity");
+            hostile_contacts.0.remove(&hostile_entity);
+            commands
+                .entity(hostile_entity)
+                .remove::<(HostileEntity, Patrol, Caster)>()
+                .insert((
+                    ColliderDisabled,
+                    Velocity::zero(),
+                    Defeated(Timer::from_seconds(DEFEAT_ANIMATION_TIME, TimerMode::Once)),
+                ));
+        }
+
+        velocity.linvel.y = movement_config.stomp_bounce_velocity;
+        // The bounce isn't cut short by releasing jump
+        jump_state.is_jumping = false;
+
+        commands.spawn((
+            AudioPlayer(audio_assets.jump_boost.clone()),
+            PlaybackSettings::REMOVE,
+        ));
+    }
+}
+
+/// Squashes and fades out stomped hostiles, then despawns them
+fn animate_defeat(
+    mut query: Query<(Entity, &mut Transform, &mut Sprite, &mut Defeated)>,
+    time: Res<Time>,
+    mut commands: Commands,
+) {
+    for (entity, mut transform, mut sprite, mut defeated) in &mut query {
+        defeated.0.tick(time.delta());
+
+        if defeated.0.finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+
+        let left = 1. - defeated.0.fraction();
+        transform.scale.y = left;
+        sprite.color.set_alpha(left);
+    }
+}
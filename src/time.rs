@@ -1,7 +1,14 @@
-use crate::{assets::FontAssets, screens::despawn_screen, GameState};
+use crate::{
+    assets::FontAssets,
+    save::{self, SaveData},
+    screens::despawn_screen,
+    GameState,
+};
 use bevy::prelude::*;
 use bevy::time::Stopwatch;
 use bevy::utils::{Duration, HashMap};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
 
 pub struct TimeTakenPlugin;
 
@@ -10,6 +17,7 @@ impl Plugin for TimeTakenPlugin {
         app.add_event::<RestartTimeEvent>()
             .add_event::<RecordTimeEvent>()
             .insert_resource(TimeTakenRes::default())
+            .add_systems(Startup, load_best_times)
             .add_systems(Update, (restart_time, record_final_time))
             .add_systems(
                 Update,
@@ -31,11 +39,63 @@ pub struct RestartTimeEvent;
 #[derive(Event)]
 pub struct RecordTimeEvent(pub i32);
 
+/// Per level progress of the player: the best times, and the collectibles that were
+/// picked up which are managed by [`crate::collectible`]
 #[derive(Resource, Debug, better_default::Default)]
 #[default(stopwatch: Stopwatch::new())]
 pub struct TimeTakenRes {
     pub all_times: HashMap<i32, Duration>,
     pub stopwatch: Stopwatch,
+    /// Level Number -> Iids of the collectibles picked up in that level
+    pub collected: HashMap<i32, BTreeSet<String>>,
+    /// Level Number -> Iids of the collectibles picked up since the level was started,
+    /// they only count as `collected` once the level is finished
+    pub collecting: HashMap<i32, BTreeSet<String>>,
+    /// Level Number -> Number of collectibles placed in that level
+    pub collectible_totals: HashMap<i32, usize>,
+}
+
+impl TimeTakenRes {
+    pub fn collected_count(&self, level_id: i32) -> usize {
+        self.collected.get(&level_id).map_or(0, BTreeSet::len)
+    }
+
+    /// Includes the collectibles that don't count yet, as the level isn't finished
+    pub fn picked_up_count(&self, level_id: i32) -> usize {
+        self.collected_count(level_id) + self.collecting.get(&level_id).map_or(0, BTreeSet::len)
+    }
+
+    pub fn collectible_total(&self, level_id: i32) -> usize {
+        self.collectible_totals
+            .get(&level_id)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// i.e. `2/5`
+    pub fn collection_progress_text(&self, level_id: i32) -> String {
+        format!(
+            "{}/{}",
+            self.collected_count(level_id),
+            self.collectible_total(level_id)
+        )
+    }
+}
+
+/// Persisted version of [`TimeTakenRes::all_times`]
+#[derive(Serialize, Deserialize, Default)]
+pub struct BestTimes {
+    pub times: BTreeMap<i32, Duration>,
+}
+
+impl SaveData for BestTimes {
+    const KEY: &'static str = "best_times";
+    const VERSION: u32 = 1;
+}
+
+fn load_best_times(mut time_taken_res: ResMut<TimeTakenRes>) {
+    let best_times = save::load::<BestTimes>();
+    time_taken_res.all_times = best_times.times.into_iter().collect();
 }
 
 fn restart_time(
@@ -60,14 +120,30 @@ fn record_final_time(
         log::info!("Recoding Final Time");
         let time_taken = time_taken_res.stopwatch.elapsed();
 
-        if let Some(exisiting_best_time) = time_taken_res.all_times.get_mut(&level_finished.0) {
+        let is_new_record = if let Some(exisiting_best_time) =
+            time_taken_res.all_times.get_mut(&level_finished.0)
+        {
             if time_taken < *exisiting_best_time {
                 *exisiting_best_time = time_taken;
+                true
+            } else {
+                false
             }
         } else {
             time_taken_res
                 .all_times
                 .insert(level_finished.0, time_taken);
+            true
+        };
+
+        if is_new_record {
+            save::store(&BestTimes {
+                times: time_taken_res
+                    .all_times
+                    .iter()
+                    .map(|(level_id, time)| (*level_id, *time))
+                    .collect(),
+            });
         }
 
         // Pause The Clock
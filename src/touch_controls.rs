@@ -0,0 +1,255 @@
+use crate::{
+    camera::MainCamera,
+    input::{ActionInputSet, InputAction},
+    screens::despawn_screen,
+    GameState,
+};
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+use bevy::utils::HashSet;
+use bevy::window::PrimaryWindow;
+
+/// On-screen controls for devices without a keyboard, i.e. mobile browsers.
+///
+/// The overlay is shown as soon as the screen is touched and hidden again once
+/// a keyboard or gamepad is used.
+pub struct TouchControlsPlugin;
+
+impl Plugin for TouchControlsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(TouchControls::default())
+            .insert_resource(TouchActions::default())
+            .add_systems(
+                PreUpdate,
+                (detect_touch_device, update_touch_actions)
+                    .chain()
+                    .after(InputSystem)
+                    .before(ActionInputSet),
+            )
+            .add_systems(
+                OnExit(GameState::PlayingScreen),
+                (
+                    despawn_screen::<TouchControlsContext>,
+                    release_touch_actions,
+                ),
+            )
+            .add_systems(
+                Update,
+                toggle_touch_controls.run_if(in_state(GameState::PlayingScreen)),
+            );
+    }
+}
+
+#[derive(Resource, Default, PartialEq)]
+pub struct TouchControls {
+    pub enabled: bool,
+}
+
+/// Actions that are currently held down through the on-screen controls
+#[derive(Resource, Default)]
+pub struct TouchActions {
+    pub pressed: HashSet<InputAction>,
+}
+
+#[derive(Component)]
+pub struct TouchControlsContext;
+
+#[derive(Component)]
+pub struct TouchButton(pub InputAction);
+
+fn detect_touch_device(
+    touches: Res<Touches>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    mut touch_controls: ResMut<TouchControls>,
+) {
+    if touches.any_just_pressed() {
+        touch_controls.set_if_neq(TouchControls { enabled: true });
+    } else if keyboard.get_just_pressed().next().is_some()
+        || gamepads
+            .iter()
+            .any(|gamepad| gamepad.get_just_pressed().next().is_some())
+    {
+        touch_controls.set_if_neq(TouchControls { enabled: false });
+    }
+}
+
+fn toggle_touch_controls(
+    touch_controls: Res<TouchControls>,
+    context_query: Query<Entity, With<TouchControlsContext>>,
+    commands: Commands,
+) {
+    if touch_controls.enabled && context_query.is_empty() {
+        spawn_touch_controls(commands);
+    } else if !touch_controls.enabled && !context_query.is_empty() {
+        despawn_screen::<TouchControlsContext>(context_query, commands);
+    }
+}
+
+fn spawn_touch_controls(mut commands: Commands) {
+    log::info!("Spawning Touch Controls");
+
+    commands
+        .spawn((
+            TouchControlsContext,
+            Node {
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                position_type: PositionType::Absolute,
+                left: Val::Px(0.),
+                top: Val::Px(0.),
+                ..default()
+            },
+            // Touches are hit tested in `update_touch_actions`, so the
+            // overlay shouldn't capture any interactions
+            FocusPolicy::Pass,
+        ))
+        .with_children(|parent| {
+            spawn_touch_button(
+                parent,
+                InputAction::MoveLeft,
+                "<",
+                110.,
+                UiRect {
+                    left: Val::Px(30.),
+                    bottom: Val::Px(30.),
+                    ..default()
+                },
+            );
+            spawn_touch_button(
+                parent,
+                InputAction::MoveRight,
+                ">",
+                110.,
+                UiRect {
+                    left: Val::Px(170.),
+                    bottom: Val::Px(30.),
+                    ..default()
+                },
+            );
+            spawn_touch_button(
+                parent,
+                InputAction::Jump,
+                "^",
+                110.,
+                UiRect {
+                    right: Val::Px(30.),
+                    bottom: Val::Px(30.),
+                    ..default()
+                },
+            );
+            spawn_touch_button(
+                parent,
+                InputAction::Dash,
+                ">>",
+                90.,
+                UiRect {
+                    right: Val::Px(160.),
+                    bottom: Val::Px(40.),
+                    ..default()
+                },
+            );
+            spawn_touch_button(
+                parent,
+                InputAction::Pause,
+                "II",
+                70.,
+                // Above the jump button, as the top of the screen is used by
+                // the time, health bar and tutorial overlays
+                UiRect {
+                    right: Val::Px(50.),
+                    bottom: Val::Px(170.),
+                    ..default()
+                },
+            );
+        });
+}
+
+fn spawn_touch_button(
+    parent: &mut ChildBuilder,
+    action: InputAction,
+    label: &str,
+    size: f32,
+    position: UiRect,
+) {
+    parent
+        .spawn((
+            TouchButton(action),
+            Node {
+                width: Val::Px(size),
+                height: Val::Px(size),
+                position_type: PositionType::Absolute,
+                left: position.left,
+                right: position.right,
+                top: position.top,
+                bottom: position.bottom,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            // White With 20% Opacity
+            BackgroundColor(Color::hsla(0., 1., 1., 0.2)),
+            BorderRadius::all(Val::Percent(50.)),
+            FocusPolicy::Pass,
+        ))
+        .with_child((
+            Text::new(label),
+            TextColor(Color::WHITE),
+            TextFont {
+                font_size: 40.,
+                ..default()
+            },
+        ));
+}
+
+/// Hit tests every active touch against the touch buttons, several buttons can be
+/// held at the same time i.e. running and jumping
+fn update_touch_actions(
+    touches: Res<Touches>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<&Camera, With<MainCamera>>,
+    mut button_query: Query<(
+        &TouchButton,
+        &ComputedNode,
+        &GlobalTransform,
+        &mut BackgroundColor,
+    )>,
+    mut touch_actions: ResMut<TouchActions>,
+) {
+    touch_actions.pressed.clear();
+
+    let Ok(window) = window_query.get_single() else {
+        return;
+    };
+
+    // UI nodes are positioned in physical pixels relative to the camera viewport
+    let viewport_position = camera_query
+        .get_single()
+        .ok()
+        .and_then(Camera::physical_viewport_rect)
+        .map(|rect| rect.min.as_vec2())
+        .unwrap_or_default();
+
+    for (touch_button, computed_node, transform, mut background_color) in &mut button_query {
+        let node_rect =
+            Rect::from_center_size(transform.translation().truncate(), computed_node.size());
+
+        let is_pressed = touches.iter().any(|touch| {
+            node_rect.contains(touch.position() * window.scale_factor() - viewport_position)
+        });
+
+        if is_pressed {
+            touch_actions.pressed.insert(touch_button.0);
+        }
+
+        let alpha = if is_pressed { 0.5 } else { 0.2 };
+        if background_color.0.alpha() != alpha {
+            background_color.0.set_alpha(alpha);
+        }
+    }
+}
+
+fn release_touch_actions(mut touch_actions: ResMut<TouchActions>) {
+    touch_actions.pressed.clear();
+}
@@ -1,5 +1,6 @@
 use crate::{
     assets::{AssetsLoadingState, EntitySpriteAssets, FontAssets, IconsAssets},
+    input::{key_name, InputAction, InputBindings},
     level_manager::CurrentLevelInfo,
     screens::despawn_screen,
     sprite_animation::Animation,
@@ -58,9 +59,9 @@ pub enum TutorialState {
 
 #[derive(Resource, Default)]
 pub struct TutorialInfo {
-    pub has_pressed_d_key: bool,
-    pub has_pressed_a_key: bool,
-    pub has_pressed_space_bar: bool,
+    pub has_moved_right: bool,
+    pub has_moved_left: bool,
+    pub has_jumped: bool,
 }
 
 #[derive(Component)]
@@ -85,20 +86,23 @@ fn set_tutorial_state(
     }
 }
 
-fn tutorial_progress(input: Res<ButtonInput<KeyCode>>, mut tutorial_info: ResMut<TutorialInfo>) {
+fn tutorial_progress(
+    input: Res<ButtonInput<InputAction>>,
+    mut tutorial_info: ResMut<TutorialInfo>,
+) {
     // This ensures that user will go through each tutorial step in order
     #[allow(clippy::collapsible_if)]
-    if !tutorial_info.has_pressed_d_key {
-        if input.pressed(KeyCode::KeyD) {
-            tutorial_info.has_pressed_d_key = true;
+    if !tutorial_info.has_moved_right {
+        if input.pressed(InputAction::MoveRight) {
+            tutorial_info.has_moved_right = true;
         }
-    } else if !tutorial_info.has_pressed_a_key {
-        if input.pressed(KeyCode::KeyA) {
-            tutorial_info.has_pressed_a_key = true;
+    } else if !tutorial_info.has_moved_left {
+        if input.pressed(InputAction::MoveLeft) {
+            tutorial_info.has_moved_left = true;
         }
-    } else if !tutorial_info.has_pressed_space_bar {
-        if input.pressed(KeyCode::Space) {
-            tutorial_info.has_pressed_space_bar = true;
+    } else if !tutorial_info.has_jumped {
+        if input.pressed(InputAction::Jump) {
+            tutorial_info.has_jumped = true;
         }
     }
 }
@@ -107,18 +111,10 @@ fn spawn_basic_keycodes_overlay(
     mut commands: Commands,
     icons_assets: Res<IconsAssets>,
     font_assets: Res<FontAssets>,
+    bindings: Res<InputBindings>,
 ) {
     let font = &font_assets.default_font;
 
-    let keyboard_w = &icons_assets.keyboard_w;
-    let keyboard_a = &icons_assets.keyboard_a;
-    let keyboard_s = &icons_assets.keyboard_s;
-    let keyboard_d = &icons_assets.keyboard_d;
-
-    let keyboard_spacebar_1 = &icons_assets.keyboard_spacebar_1;
-    let keyboard_spacebar_2 = &icons_assets.keyboard_spacebar_2;
-    let keyboard_spacebar_3 = &icons_assets.keyboard_spacebar_3;
-
     commands
         .spawn((
             Node {
@@ -136,28 +132,7 @@ fn spawn_basic_keycodes_overlay(
             TutorialLevelSpecific(0),
         ))
         .with_children(|parent| {
-            // Spawn First row
-            parent
-                .spawn(Node {
-                    display: Display::Flex,
-                    justify_content: JustifyContent::Center,
-                    align_items: AlignItems::Center,
-                    ..default()
-                })
-                // Spawn W Key
-                .with_child((
-                    Node {
-                        width: Val::Px(50.),
-                        height: Val::Px(50.),
-                        ..default()
-                    },
-                    ImageNode {
-                        image: keyboard_w.clone(),
-                        ..default()
-                    },
-                ));
-
-            // Spawn Second Row
+            // Spawn Movement Row
             parent
                 .spawn(Node {
                     display: Display::Flex,
@@ -167,47 +142,21 @@ fn spawn_basic_keycodes_overlay(
                     ..default()
                 })
                 .with_children(|parent| {
-                    // Spawn A Key
-                    parent.spawn((
-                        Node {
-                            width: Val::Px(50.),
-                            height: Val::Px(50.),
-                            ..default()
-                        },
-                        ImageNode {
-                            image: keyboard_a.clone(),
-                            ..default()
-                        },
-                    ));
-
-                    // Spawn S Key
-                    parent.spawn((
-                        Node {
-                            width: Val::Px(50.),
-                            height: Val::Px(50.),
-                            ..default()
-                        },
-                        ImageNode {
-                            image: keyboard_s.clone(),
-                            ..default()
-                        },
-                    ));
-
-                    // Spawn D Key
-                    parent.spawn((
-                        Node {
-                            width: Val::Px(50.),
-                            height: Val::Px(50.),
-                            ..default()
-                        },
-                        ImageNode {
-                            image: keyboard_d.clone(),
-                            ..default()
-                        },
-                    ));
+                    spawn_key_icon(
+                        parent,
+                        bindings.key(InputAction::MoveLeft),
+                        &icons_assets,
+                        font,
+                    );
+                    spawn_key_icon(
+                        parent,
+                        bindings.key(InputAction::MoveRight),
+                        &icons_assets,
+                        font,
+                    );
                 });
 
-            // Spawn Third Row
+            // Spawn Jump Row
             parent
                 .spawn(Node {
                     display: Display::Flex,
@@ -217,39 +166,7 @@ fn spawn_basic_keycodes_overlay(
                     ..default()
                 })
                 .with_children(|parent| {
-                    parent.spawn((
-                        Node {
-                            width: Val::Px(50.),
-                            height: Val::Px(50.),
-                            ..default()
-                        },
-                        ImageNode {
-                            image: keyboard_spacebar_1.clone(),
-                            ..default()
-                        },
-                    ));
-                    parent.spawn((
-                        Node {
-                            width: Val::Px(50.),
-                            height: Val::Px(50.),
-                            ..default()
-                        },
-                        ImageNode {
-                            image: keyboard_spacebar_2.clone(),
-                            ..default()
-                        },
-                    ));
-                    parent.spawn((
-                        Node {
-                            width: Val::Px(50.),
-                            height: Val::Px(50.),
-                            ..default()
-                        },
-                        ImageNode {
-                            image: keyboard_spacebar_3.clone(),
-                            ..default()
-                        },
-                    ));
+                    spawn_key_icon(parent, bindings.key(InputAction::Jump), &icons_assets, font);
                 });
         });
 
@@ -266,7 +183,11 @@ fn spawn_basic_keycodes_overlay(
             TutorialLevelSpecific(0),
         ))
         .with_child((
-            Text::new("Press of `D` key to move right."),
+            Text::new(tutorial_step_text(
+                InputAction::MoveRight,
+                "to move right",
+                &bindings,
+            )),
             TextFont {
                 font: font.clone(),
                 font_size: 33.,
@@ -277,22 +198,95 @@ fn spawn_basic_keycodes_overlay(
         ));
 }
 
+/// Spawns the keyboard icon of `key`, keys without an icon are rendered as text
+fn spawn_key_icon(
+    parent: &mut ChildBuilder,
+    key: Option<KeyCode>,
+    icons_assets: &IconsAssets,
+    font: &Handle<Font>,
+) {
+    let images = match key {
+        Some(KeyCode::KeyW) => vec![&icons_assets.keyboard_w],
+        Some(KeyCode::KeyA) => vec![&icons_assets.keyboard_a],
+        Some(KeyCode::KeyS) => vec![&icons_assets.keyboard_s],
+        Some(KeyCode::KeyD) => vec![&icons_assets.keyboard_d],
+        Some(KeyCode::Space) => vec![
+            &icons_assets.keyboard_spacebar_1,
+            &icons_assets.keyboard_spacebar_2,
+            &icons_assets.keyboard_spacebar_3,
+        ],
+        _ => Vec::new(),
+    };
+
+    if images.is_empty() {
+        parent
+            .spawn((
+                Node {
+                    min_width: Val::Px(50.),
+                    height: Val::Px(50.),
+                    padding: UiRect::horizontal(Val::Px(10.)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    border: UiRect::all(Val::Px(3.)),
+                    ..default()
+                },
+                BorderColor(Color::WHITE),
+                BorderRadius::all(Val::Px(5.)),
+            ))
+            .with_child((
+                Text::new(key.map(key_name).unwrap_or(String::from("?"))),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 28.,
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+            ));
+        return;
+    }
+
+    for image in images {
+        parent.spawn((
+            Node {
+                width: Val::Px(50.),
+                height: Val::Px(50.),
+                ..default()
+            },
+            ImageNode {
+                image: image.clone(),
+                ..default()
+            },
+        ));
+    }
+}
+
+fn tutorial_step_text(action: InputAction, description: &str, bindings: &InputBindings) -> String {
+    match bindings.key(action) {
+        Some(KeyCode::Space) => format!("Press the Space Bar key {}.", description),
+        Some(key) => format!("Press of `{}` key {}.", key_name(key), description),
+        None => format!("Bind a key to {} in Settings.", action.label()),
+    }
+}
+
 fn update_tutorial_context(
     tutorial_info: Res<TutorialInfo>,
     mut tutorial_text: Query<&mut Text, With<TutorialText>>,
     mut next_tutorial_state: ResMut<NextState<TutorialState>>,
     mut commands: Commands,
     font_assets: Res<FontAssets>,
+    bindings: Res<InputBindings>,
 ) {
     if tutorial_info.is_changed() {
         log::info!("Updating Tutorial Text Context");
         for mut tutorial_text in &mut tutorial_text {
-            if !tutorial_info.has_pressed_d_key {
-                tutorial_text.0 = String::from("Press of `D` key to move right.");
-            } else if !tutorial_info.has_pressed_a_key {
-                tutorial_text.0 = String::from("Press of `A` key to move left.");
-            } else if !tutorial_info.has_pressed_space_bar {
-                tutorial_text.0 = String::from("Press the Space Bar key to jump.");
+            if !tutorial_info.has_moved_right {
+                tutorial_text.0 =
+                    tutorial_step_text(InputAction::MoveRight, "to move right", &bindings);
+            } else if !tutorial_info.has_moved_left {
+                tutorial_text.0 =
+                    tutorial_step_text(InputAction::MoveLeft, "to move left", &bindings);
+            } else if !tutorial_info.has_jumped {
+                tutorial_text.0 = tutorial_step_text(InputAction::Jump, "to jump", &bindings);
             } else {
                 log::info!("Tutorial Finished");
                 next_tutorial_state.set(TutorialState::Finished);
@@ -2,6 +2,7 @@ use bevy::{
     ecs::{component::ComponentId, world::DeferredWorld},
     prelude::*,
 };
+use bevy_ecs_ldtk::{ldtk::ldtk_fields::LdtkFieldsError, prelude::*};
 use std::marker::PhantomData;
 
 pub struct Maybe<B: Bundle>(pub Option<B>);
@@ -72,3 +73,71 @@ impl<B: Bundle> Command for MaybeCommand<B> {
         }
     }
 }
+
+/// Value of an optional LDtk Float field, missing and null fields are `None`.
+///
+/// Panics if the field has another type, as that is a mistake in the LDtk project.
+pub fn maybe_float_field(entity_instance: &EntityInstance, identifier: &str) -> Option<f32> {
+    match entity_instance.get_maybe_float_field(identifier) {
+        Ok(value) => *value,
+        Err(LdtkFieldsError::FieldNotFound { .. }) => None,
+        Err(err) => panic!(
+            "{}",
+            field_error(entity_instance, identifier, "a Float", err)
+        ),
+    }
+}
+
+/// Same as [`maybe_float_field`] for Int fields
+pub fn maybe_int_field(entity_instance: &EntityInstance, identifier: &str) -> Option<i32> {
+    match entity_instance.get_maybe_int_field(identifier) {
+        Ok(value) => *value,
+        Err(LdtkFieldsError::FieldNotFound { .. }) => None,
+        Err(err) => panic!(
+            "{}",
+            field_error(entity_instance, identifier, "an Int", err)
+        ),
+    }
+}
+
+/// Same as [`maybe_float_field`] for Enum fields
+pub fn maybe_enum_field<'a>(
+    entity_instance: &'a EntityInstance,
+    identifier: &str,
+) -> Option<&'a str> {
+    match entity_instance.get_maybe_enum_field(identifier) {
+        Ok(value) => value.as_deref(),
+        Err(LdtkFieldsError::FieldNotFound { .. }) => None,
+        Err(err) => panic!(
+            "{}",
+            field_error(entity_instance, identifier, "an Enum", err)
+        ),
+    }
+}
+
+/// Same as [`maybe_float_field`] for Array<Float> fields, a missing field is empty
+pub fn maybe_floats_field<'a>(
+    entity_instance: &'a EntityInstance,
+    identifier: &str,
+) -> &'a [Option<f32>] {
+    match entity_instance.get_maybe_floats_field(identifier) {
+        Ok(values) => values,
+        Err(LdtkFieldsError::FieldNotFound { .. }) => &[],
+        Err(err) => panic!(
+            "{}",
+            field_error(entity_instance, identifier, "an Array<Float>", err)
+        ),
+    }
+}
+
+fn field_error(
+    entity_instance: &EntityInstance,
+    identifier: &str,
+    expected_type: &str,
+    err: LdtkFieldsError,
+) -> String {
+    format!(
+        "Field `{}` of {} {} should be {}: {}",
+        identifier, entity_instance.identifier, entity_instance.iid, expected_type, err
+    )
+}
@@ -1,14 +1,18 @@
 use bevy::{prelude::*, utils::HashMap};
-use bevy_ecs_ldtk::prelude::*;
+use bevy_ecs_ldtk::{prelude::*, utils::grid_coords_to_translation};
 use bevy_ecs_tilemap::{map::TilemapId, tiles::TileBundle};
 use bevy_light_2d::light::PointLight2d;
 use bevy_rapier2d::prelude::*;
+use serde::Deserialize;
 
 use crate::{
     assets::{AssetsLoadingState, IconsAssets},
+    config::RonConfigPlugin,
+    ground_detection::{GroundNormal, GroundSensor, SlipperyGround},
     hostile_entity::{DamageCount, HostileEntity},
-    level_manager::CurrentLevelInfo,
-    player::PlayerEntity,
+    level_manager::{CurrentLevelInfo, LevelProgress},
+    player::{PlayerEntity, PLAYER_HEIGHT},
+    save,
     special_tiles::SpikeEntity,
     sprite_animation::Animation,
     time::RecordTimeEvent,
@@ -20,20 +24,33 @@ pub struct WallPlugin;
 
 impl Plugin for WallPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            Update,
-            (
-                spawn_wall_collisions,
-                despawn_tile_bundle,
-                read_collisions,
-                next_level_hint,
+        app.add_plugins(RonConfigPlugin::<TilesConfig>::new("config/tiles.ron"))
+            .add_systems(
+                Update,
+                (
+                    spawn_wall_collisions,
+                    despawn_tile_bundle,
+                    read_collisions,
+                    next_level_hint,
+                )
+                    .chain()
+                    .run_if(in_state(AssetsLoadingState::Loaded)),
             )
-                .chain()
-                .run_if(in_state(AssetsLoadingState::Loaded)),
-        )
-        .register_ldtk_int_cell_for_layer::<Wall<WallEntity>>("Collider", 1)
-        .register_ldtk_int_cell_for_layer::<Wall<OutOfWorldEntity>>("Collider", 2)
-        .register_ldtk_int_cell_for_layer::<Wall<NextLevelEntity>>("Collider", 3);
+            .register_ldtk_int_cell_for_layer::<Wall<WallEntity>>("Collider", 1)
+            .register_ldtk_int_cell_for_layer::<Wall<OutOfWorldEntity>>("Collider", 2)
+            .register_ldtk_int_cell_for_layer::<Wall<NextLevelEntity>>("Collider", 3)
+            .register_ldtk_int_cell_for_layer::<Wall<OneWayEntity>>("Collider", 10)
+            .register_ldtk_int_cell_for_layer::<Wall<CrumblingEntity>>("Collider", 11)
+            .register_ldtk_int_cell_for_layer::<Wall<IceEntity>>("Collider", 12)
+            .add_systems(
+                Update,
+                (update_one_way_platforms, crumble_platforms)
+                    .run_if(in_state(GameState::PlayingScreen)),
+            );
+
+        for value in 4..=7 {
+            app.register_ldtk_int_cell_for_layer::<SlopeWall>("Collider", value);
+        }
     }
 }
 
@@ -46,6 +63,14 @@ pub struct Wall<C: Component + Default> {
     global_wall: GlobalWallEntity,
 }
 
+/// Same as [`Wall`] but remembers which kind of slope the IntCell was
+#[derive(Default, Bundle, LdtkIntCell)]
+pub struct SlopeWall {
+    #[from_int_grid_cell]
+    slope_entity: SlopeEntity,
+    global_wall: GlobalWallEntity,
+}
+
 #[derive(Default, Component)]
 pub struct OutOfWorldEntity;
 
@@ -55,15 +80,136 @@ pub struct WallEntity;
 #[derive(Default, Component)]
 pub struct NextLevelEntity;
 
+#[derive(Default, Component)]
+pub struct OneWayEntity;
+
+#[derive(Default, Component)]
+pub struct CrumblingEntity;
+
+#[derive(Default, Component)]
+pub struct IceEntity;
+
 #[derive(Default, Component)]
 pub struct GlobalWallEntity;
 
+/// Rise of a 22.5° slope per tile, tan(22.5°)
+const SHALLOW_SLOPE_RISE: f32 = std::f32::consts::SQRT_2 - 1.;
+
+/// Diagonal wall tiles, named after the direction the slope rises towards.
+///
+/// 45° slopes are a triangle in every tile. 22.5° (`Shallow`) slopes don't rise a whole
+/// tile per tile, so every row of them is merged into a single slope instead.
+#[derive(Default, Component, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SlopeEntity {
+    #[default]
+    UpRight,
+    UpLeft,
+    ShallowUpRight,
+    ShallowUpLeft,
+}
+
+impl From<IntGridCell> for SlopeEntity {
+    fn from(value: IntGridCell) -> Self {
+        match value.value {
+            4 => SlopeEntity::UpRight,
+            5 => SlopeEntity::UpLeft,
+            6 => SlopeEntity::ShallowUpRight,
+            7 => SlopeEntity::ShallowUpLeft,
+            _ => panic!("Unsupported IntGridCell value for slope"),
+        }
+    }
+}
+
+impl SlopeEntity {
+    fn is_shallow(self) -> bool {
+        matches!(
+            self,
+            SlopeEntity::ShallowUpRight | SlopeEntity::ShallowUpLeft
+        )
+    }
+
+    fn rises_right(self) -> bool {
+        matches!(self, SlopeEntity::UpRight | SlopeEntity::ShallowUpRight)
+    }
+
+    /// Tiles the slope rises per tile
+    fn rise(self) -> f32 {
+        if self.is_shallow() {
+            SHALLOW_SLOPE_RISE
+        } else {
+            1.
+        }
+    }
+
+    /// Unit normal of the walkable surface
+    fn normal(self) -> Vec2 {
+        let normal = Vec2::new(-self.rise(), 1.).normalize();
+
+        if self.rises_right() {
+            normal
+        } else {
+            Vec2::new(-normal.x, normal.y)
+        }
+    }
+
+    /// Solid parts of a row of `width` tiles of this slope, relative to the bottom left
+    /// corner of the row: the vertices of the slope, and the flat wall after the slope
+    /// reached the top of the row.
+    ///
+    /// A 22.5° slope needs 1 / (√2 - 1) ≈ 2.4 tiles to rise a whole tile, shorter rows end
+    /// below the top of the row.
+    fn run_colliders(self, width: i32, grid_size: f32) -> (Vec<Vec2>, Option<Rect>) {
+        let width = width as f32 * grid_size;
+        let slope_width = (grid_size / self.rise()).min(width);
+
+        let slope = [
+            Vec2::ZERO,
+            Vec2::new(slope_width, 0.),
+            Vec2::new(slope_width, slope_width * self.rise()),
+        ];
+        let flat = (slope_width < width).then(|| Rect::new(slope_width, 0., width, grid_size));
+
+        if self.rises_right() {
+            (slope.to_vec(), flat)
+        } else {
+            let mirror = |point: Vec2| Vec2::new(width - point.x, point.y);
+            (
+                slope.map(mirror).to_vec(),
+                flat.map(|flat| Rect::from_corners(mirror(flat.min), mirror(flat.max))),
+            )
+        }
+    }
+}
+
 #[derive(Default, Component)]
 pub struct NextLevelTrigger;
 
 #[derive(Default, Component)]
 pub struct OutOfWorldTrigger;
 
+/// Can be jumped up through from below, see [`update_one_way_platforms`]
+#[derive(Default, Component)]
+pub struct OneWayPlatform;
+
+/// Tunes the special Collider tiles, loaded from `assets/config/tiles.ron`
+#[derive(Asset, TypePath, Resource, Deserialize, Debug, Clone, better_default::Default)]
+#[default(crumble_delay: 0.5, crumble_respawn_time: 3.)]
+pub struct TilesConfig {
+    /// Seconds a crumbling platform holds after being stood on
+    pub crumble_delay: f32,
+    /// Seconds until a fallen crumbling platform comes back
+    pub crumble_respawn_time: f32,
+}
+
+/// Every crumbling tile falls on its own, see [`crumble_platforms`]
+#[derive(Default, Component)]
+pub enum CrumblingPlatform {
+    #[default]
+    Intact,
+    Crumbling(Timer),
+    Fallen(Timer),
+}
+
 // This system is inspired from platformer example in `bevy_ecs_ldtk` and is modified
 // to add specific components based on which IntCell was that wall of.
 // Code: https://github.com/Trouv/bevy_ecs_ldtk/blob/main/examples/platformer/walls.rs#L32
@@ -74,9 +220,10 @@ pub fn spawn_wall_collisions(
         (
             &GridCoords,
             &Parent,
-            Option<&OutOfWorldEntity>,
-            Option<&NextLevelEntity>,
+            &IntGridCell,
             Option<&SpikeEntity>,
+            Option<&SlopeEntity>,
+            Has<CrumblingEntity>,
         ),
         Added<GlobalWallEntity>,
     >,
@@ -95,6 +242,8 @@ pub fn spawn_wall_collisions(
         /// 1 => Normal Wall with collider
         /// 2 => Game Over Wall collider
         /// 3 => Next Level Trigger Wall Collider
+        /// 10 => One-Way Platform
+        /// 12 => Ice
         /// 99 => Spike
         origin: i32,
     }
@@ -117,17 +266,39 @@ pub fn spawn_wall_collisions(
     // 2. it lets us easily add the collision entities as children of the appropriate level entity
     let mut level_to_wall_locations: HashMap<Entity, HashMap<GridCoords, i32>> = HashMap::new();
 
+    // Slopes can't be merged into rectangles, so they get their own colliders
+    let mut level_to_slopes: HashMap<Entity, Vec<(GridCoords, SlopeEntity)>> = HashMap::new();
+
+    // Crumbling tiles fall one by one, so they aren't merged either
+    let mut level_to_crumbling_tiles: HashMap<Entity, Vec<GridCoords>> = HashMap::new();
+
     wall_query.iter().for_each(
-        |(&grid_coords, parent, out_of_world, next_level_entity, spike_entity)| {
+        |(&grid_coords, parent, int_grid_cell, spike_entity, slope_entity, is_crumbling)| {
             // An intgrid tile's direct parent will be a layer entity, not the level entity
             // To get the level entity, you need the tile's grandparent.
             // This is where parent_query comes in.
             if let Ok(grandparent) = parent_query.get(parent.get()) {
-                let int_cell_id = match (out_of_world, next_level_entity, spike_entity) {
-                    (Some(_), None, None) => 2,
-                    (None, Some(_), None) => 3,
-                    (None, None, Some(_)) => 99,
-                    _ => 1,
+                if let Some(&slope_entity) = slope_entity {
+                    level_to_slopes
+                        .entry(grandparent.get())
+                        .or_default()
+                        .push((grid_coords, slope_entity));
+                    return;
+                }
+
+                if is_crumbling {
+                    level_to_crumbling_tiles
+                        .entry(grandparent.get())
+                        .or_default()
+                        .push(grid_coords);
+                    return;
+                }
+
+                // Spikes are on their own layer, so their IntCell value clashes with walls
+                let int_cell_id = if spike_entity.is_some() {
+                    99
+                } else {
+                    int_grid_cell.value
                 };
                 level_to_wall_locations
                     .entry(grandparent.get())
@@ -236,16 +407,16 @@ pub fn spawn_wall_collisions(
                     // 1. Adjusts the transforms to be relative to the level for free
                     // 2. the colliders will be despawned automatically when levels unload
                     for wall_rect in wall_rects {
+                        let size = Vec2::new(
+                            (wall_rect.right as f32 - wall_rect.left as f32 + 1.)
+                                * grid_size as f32,
+                            (wall_rect.top as f32 - wall_rect.bottom as f32 + 1.)
+                                * grid_size as f32,
+                        );
+
                         let mut entity = level.spawn_empty();
                         entity
-                            .insert(Collider::cuboid(
-                                (wall_rect.right as f32 - wall_rect.left as f32 + 1.)
-                                    * grid_size as f32
-                                    / 2.,
-                                (wall_rect.top as f32 - wall_rect.bottom as f32 + 1.)
-                                    * grid_size as f32
-                                    / 2.,
-                            ))
+                            .insert(Collider::cuboid(size.x / 2., size.y / 2.))
                             .insert(RigidBody::Fixed)
                             .insert(Friction::new(1.0))
                             .insert(Transform::from_xyz(
@@ -253,7 +424,9 @@ pub fn spawn_wall_collisions(
                                     / 2.,
                                 (wall_rect.bottom + wall_rect.top + 1) as f32 * grid_size as f32
                                     / 2.,
-                                0.,
+                                // Above the tiles of the level, only matters for the tiles
+                                // that draw themselves
+                                5.,
                             ))
                             .insert(GlobalTransform::default());
 
@@ -274,11 +447,100 @@ pub fn spawn_wall_collisions(
                         if wall_rect.origin == 99 {
                             entity.insert(HostileEntity).insert(DamageCount(1));
                         }
+
+                        // The Collider layer isn't rendered, so these tiles draw themselves
+                        // to be told apart from normal walls
+                        match wall_rect.origin {
+                            10 => {
+                                entity
+                                    .insert((OneWayPlatform, SolverGroups::default()))
+                                    .with_child((
+                                        // hsl(30, 40%, 35%)
+                                        Sprite::from_color(
+                                            Color::hsl(30., 0.4, 0.35),
+                                            Vec2::new(size.x, 4.),
+                                        ),
+                                        Transform::from_xyz(0., size.y / 2. - 2., 0.),
+                                    ));
+                            }
+                            12 => {
+                                entity.insert((
+                                    SlipperyGround,
+                                    Friction::new(0.),
+                                    // hsla(195, 80%, 80%, 60%)
+                                    Sprite::from_color(Color::hsla(195., 0.8, 0.8, 0.6), size),
+                                ));
+                            }
+                            _ => {}
+                        }
                     }
                 });
             }
         });
     }
+
+    for (level_entity, crumbling_tiles) in level_to_crumbling_tiles {
+        commands.entity(level_entity).with_children(|level| {
+            for grid_coords in crumbling_tiles {
+                level.spawn((
+                    Collider::cuboid(GRID_SIZE as f32 / 2., GRID_SIZE as f32 / 2.),
+                    RigidBody::Fixed,
+                    Friction::new(1.0),
+                    CrumblingPlatform::default(),
+                    // hsl(30, 30%, 30%)
+                    Sprite::from_color(Color::hsl(30., 0.3, 0.3), Vec2::splat(GRID_SIZE as f32)),
+                    Transform::from_translation(
+                        grid_coords_to_translation(grid_coords, IVec2::splat(GRID_SIZE)).extend(5.),
+                    ),
+                ));
+            }
+        });
+    }
+
+    for (level_entity, mut slopes) in level_to_slopes {
+        // Rows of 22.5° slopes become a single slope, 45° slopes are one per tile
+        slopes.sort_by_key(|(grid_coords, _)| (grid_coords.y, grid_coords.x));
+        let mut slope_runs: Vec<(GridCoords, i32, SlopeEntity)> = Vec::new();
+
+        for (grid_coords, slope_entity) in slopes {
+            match slope_runs.last_mut() {
+                Some((start, width, run_slope))
+                    if slope_entity.is_shallow()
+                        && *run_slope == slope_entity
+                        && start.y == grid_coords.y
+                        && start.x + *width == grid_coords.x =>
+                {
+                    *width += 1;
+                }
+                _ => slope_runs.push((grid_coords, 1, slope_entity)),
+            }
+        }
+
+        commands.entity(level_entity).with_children(|level| {
+            for (start, width, slope_entity) in slope_runs {
+                let (slope, flat) = slope_entity.run_colliders(width, GRID_SIZE as f32);
+                let corner = Vec2::new(start.x as f32, start.y as f32) * GRID_SIZE as f32;
+
+                level.spawn((
+                    Collider::convex_polyline(slope)
+                        .expect("Slope vertices should form a convex polygon"),
+                    RigidBody::Fixed,
+                    Friction::new(1.0),
+                    GroundNormal(slope_entity.normal()),
+                    Transform::from_translation(corner.extend(0.)),
+                ));
+
+                if let Some(flat) = flat {
+                    level.spawn((
+                        Collider::cuboid(flat.half_size().x, flat.half_size().y),
+                        RigidBody::Fixed,
+                        Friction::new(1.0),
+                        Transform::from_translation((corner + flat.center()).extend(0.)),
+                    ));
+                }
+            }
+        });
+    }
 }
 
 // This component is essential as these tiles are just used for reference
@@ -310,6 +572,7 @@ fn read_collisions(
     next_level_trigger_query: Query<Entity, With<NextLevelTrigger>>,
     out_of_world_trigger_query: Query<Entity, With<OutOfWorldTrigger>>,
     mut current_level_info: ResMut<CurrentLevelInfo>,
+    mut level_progress: ResMut<LevelProgress>,
     mut next_game_state: ResMut<NextState<GameState>>,
     mut time: ResMut<Time<Virtual>>,
     mut record_time_event: EventWriter<RecordTimeEvent>,
@@ -328,6 +591,11 @@ fn read_collisions(
                     // Next Level
                     current_level_info.current_level_id += 1;
                     record_time_event.send(RecordTimeEvent(current_level_info.current_level_id));
+
+                    if level_progress.unlock(current_level_info.current_level_id) {
+                        log::info!("Unlocked level {}", current_level_info.current_level_id);
+                        save::store(&*level_progress);
+                    }
                 } else {
                     for out_of_world_entity in &out_of_world_trigger_query {
                         if entity_two == out_of_world_entity || entity_one == out_of_world_entity {
@@ -376,3 +644,89 @@ fn next_level_hint(
         ));
     }
 }
+
+/// One-way platforms only stop the player while it is above them and not moving up,
+/// otherwise the player passes through. Contacts are still reported, so the ground
+/// sensor works as usual.
+fn update_one_way_platforms(
+    player_query: Query<(&GlobalTransform, &Velocity), With<PlayerEntity>>,
+    mut platform_query: Query<
+        (&GlobalTransform, &Collider, &mut SolverGroups),
+        With<OneWayPlatform>,
+    >,
+) {
+    let Ok((player_transform, player_velocity)) = player_query.get_single() else {
+        return;
+    };
+
+    let player_bottom = player_transform.translation().y - PLAYER_HEIGHT / 2.;
+
+    for (platform_transform, collider, mut solver_groups) in &mut platform_query {
+        let Some(cuboid) = collider.as_cuboid() else {
+            continue;
+        };
+
+        let platform_top = platform_transform.translation().y + cuboid.half_extents().y;
+
+        // Some leeway, as the player sinks slightly into the platform while standing on it
+        let is_solid =
+            player_velocity.linvel.y <= 0. && player_bottom >= platform_top - GRID_SIZE as f32 / 4.;
+
+        solver_groups.set_if_neq(if is_solid {
+            SolverGroups::default()
+        } else {
+            SolverGroups::new(Group::NONE, Group::NONE)
+        });
+    }
+}
+
+/// Crumbling platforms start crumbling once something stands on them, fall after
+/// [`TilesConfig::crumble_delay`] and come back after [`TilesConfig::crumble_respawn_time`]
+fn crumble_platforms(
+    mut platform_query: Query<(Entity, &mut CrumblingPlatform, &mut Sprite, &mut Visibility)>,
+    ground_sensor_query: Query<&GroundSensor>,
+    tiles_config: Res<TilesConfig>,
+    time: Res<Time>,
+    mut commands: Commands,
+) {
+    for (entity, mut crumbling_platform, mut sprite, mut visibility) in &mut platform_query {
+        match crumbling_platform.as_mut() {
+            CrumblingPlatform::Intact => {
+                let is_stood_on = ground_sensor_query
+                    .iter()
+                    .any(|sensor| sensor.intersecting_ground_entities.contains(&entity));
+
+                if is_stood_on {
+                    *crumbling_platform = CrumblingPlatform::Crumbling(Timer::from_seconds(
+                        tiles_config.crumble_delay,
+                        TimerMode::Once,
+                    ));
+                }
+            }
+            CrumblingPlatform::Crumbling(timer) => {
+                timer.tick(time.delta());
+                sprite.color.set_alpha(1. - timer.fraction() * 0.7);
+
+                if timer.finished() {
+                    commands.entity(entity).insert(ColliderDisabled);
+                    *visibility = Visibility::Hidden;
+
+                    *crumbling_platform = CrumblingPlatform::Fallen(Timer::from_seconds(
+                        tiles_config.crumble_respawn_time,
+                        TimerMode::Once,
+                    ));
+                }
+            }
+            CrumblingPlatform::Fallen(timer) => {
+                timer.tick(time.delta());
+
+                if timer.finished() {
+                    commands.entity(entity).remove::<ColliderDisabled>();
+                    *visibility = Visibility::Inherited;
+                    sprite.color.set_alpha(1.);
+                    *crumbling_platform = CrumblingPlatform::Intact;
+                }
+            }
+        }
+    }
+}